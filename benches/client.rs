@@ -0,0 +1,58 @@
+//! Cost of cloning the client and building requests from it, as done for every call in a send loop.
+//!
+//! Run with `cargo bench --bench client`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use guilded_rs::channel::ChannelId;
+use guilded_rs::fixtures;
+
+const ITERATIONS: usize = 100_000;
+
+/// Counts allocations so changes to what a clone copies show up even when they're too fast to time
+struct Counting;
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn measure(name: &str, mut f: impl FnMut()) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    println!(
+        "{name:<24} {:>8.1} ns/iter {:>8.2} allocations/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64,
+        allocations as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    let client = fixtures::client();
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+
+    measure("clone client", || {
+        black_box(client.clone());
+    });
+    measure("create request", || {
+        black_box(client.send_message(&channel, "hello"));
+    });
+    measure("build request", || {
+        black_box(client.send_message(&channel, "hello").dry_run().unwrap());
+    });
+}
@@ -0,0 +1,36 @@
+use reqwest::Client;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::error::Result;
+
+/// Download of a file hosted outside of the API, such as an avatar or an attachment on the CDN
+#[derive(Debug)]
+pub struct DownloadRequest<'a> {
+    client: Client,
+    url: &'a str,
+}
+impl<'a> DownloadRequest<'a> {
+    pub fn new(client: Client, url: &'a str) -> Self {
+        Self { client, url }
+    }
+    /// Streams the file into `writer` and returns the number of bytes written
+    pub async fn send<W: AsyncWrite + Unpin + ?Sized>(self, writer: &mut W) -> Result<u64> {
+        let request = self.client.get(self.url).build()?;
+        let mut response = self.client.execute(request).await?.error_for_status()?;
+        let mut written = 0;
+        while let Some(chunk) = response.chunk().await? {
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+
+        Ok(written)
+    }
+    /// Reads the whole file into memory
+    pub async fn bytes(self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.send(&mut bytes).await?;
+
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,104 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Request, StatusCode};
+use serde::Serialize;
+
+/// Change made through the client, as recorded in the audit log.
+///
+/// Guilded doesn't keep an audit log of its own, so this is the only record of what the bot did.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntry {
+    time: DateTime<Utc>,
+    method: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    /// `None` when no response was received
+    status: Option<u16>,
+}
+impl AuditEntry {
+    pub(crate) fn new(time: DateTime<Utc>, request: &Request) -> Self {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+        Self {
+            time,
+            method: request.method().to_string(),
+            path: request.url().path().to_owned(),
+            body,
+            status: None,
+        }
+    }
+    pub(crate) fn set_status(&mut self, status: Option<StatusCode>) {
+        self.status = status.map(|status| status.as_u16());
+    }
+    /// When the request was sent
+    pub fn time(&self) -> &DateTime<Utc> {
+        &self.time
+    }
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+    /// API route the request went to, e.g. `/api/v1/servers/{serverId}/bans/{userId}`
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// Serialized request body, which holds details such as the ban reason
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+    /// Whether the API accepted the change
+    pub fn succeeded(&self) -> bool {
+        self.status
+            .is_some_and(|status| (200..300).contains(&status))
+    }
+}
+
+/// Destination for [`AuditEntry`]s, set with [`GuildedClientBuilder::audit`](crate::GuildedClientBuilder::audit)
+pub trait AuditSink: Send + Sync {
+    fn record(&self, entry: &AuditEntry);
+}
+impl<F: Fn(&AuditEntry) + Send + Sync> AuditSink for F {
+    fn record(&self, entry: &AuditEntry) {
+        self(entry)
+    }
+}
+
+/// Appends entries to a file, one JSON object per line
+#[derive(Debug)]
+pub struct FileSink {
+    path: PathBuf,
+    /// Keeps lines from concurrent requests from interleaving
+    lock: Mutex<()>,
+}
+impl FileSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+}
+impl AuditSink for FileSink {
+    fn record(&self, entry: &AuditEntry) {
+        let Ok(mut line) = serde_json::to_vec(entry) else {
+            return;
+        };
+        line.push(b'\n');
+        let _guard = self.lock.lock().unwrap();
+        // The audit log must never fail the request it records
+        let _ = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&line));
+    }
+}
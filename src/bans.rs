@@ -1,12 +1,24 @@
+use std::sync::Arc;
+
 use async_stream::stream;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tokio_stream::Stream;
 
-use crate::error::Result;
+use crate::dry_run::DryRun;
+use crate::error::{check_length, Error, ResponseExt, Result};
+use crate::http::Http;
 use crate::member::{ServerId, UserId, UserSummary};
-use crate::API_BASE;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
+use crate::streams::ApiStream;
+use crate::GuildedClient;
+
+/// Maximum number of characters in a ban reason
+pub const MAX_BAN_REASON_LENGTH: usize = 250;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +30,28 @@ pub struct ServerMemberBan {
     #[serde(rename = "createdAt")]
     created: DateTime<Utc>,
 }
+impl ServerMemberBan {
+    pub fn user(&self) -> &UserSummary {
+        &self.user
+    }
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+    pub fn created_by(&self) -> &UserId {
+        &self.created_by
+    }
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    /// Lifts the ban in `server`, e.g. once a temporary ban has run out
+    pub async fn revoke(&self, client: &GuildedClient, server: &ServerId) -> Result<()> {
+        client.delete_ban(server, self.user.id()).send().await
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -25,25 +59,20 @@ struct ServerBanResponse {
     #[serde(rename = "serverMemberBan")]
     ban: ServerMemberBan,
 }
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Serialize)]
 struct ServerBanBody<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     reason: Option<&'a str>,
 }
-impl<'a> ServerBanBody<'a> {
-    pub fn new(reason: Option<&'a str>) -> Self {
-        Self { reason }
-    }
-}
 #[derive(Debug)]
 pub struct ServerBanRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
     reason: Option<&'a str>,
 }
 impl<'a> ServerBanRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId) -> Self {
         Self {
             client,
             server,
@@ -52,24 +81,129 @@ impl<'a> ServerBanRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<ServerMemberBan> {
-        let body = ServerBanBody::new(self.reason);
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        if let Some(reason) = self.reason {
+            check_length(reason, 0, MAX_BAN_REASON_LENGTH).map_err(|constraint| {
+                Error::Validation {
+                    field: "reason",
+                    constraint,
+                    value: reason.to_owned(),
+                }
+            })?;
+        }
         let request = self
             .client
-            .post(format!(
-                "{API_BASE}/servers/{}/bans/{}",
-                self.server, self.user
-            ))
-            .json(&body)
+            .post(Route::Ban {
+                server: self.server,
+                user: self.user,
+            })
+            .json(&ServerBanBody {
+                reason: self.reason,
+            })
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ServerMemberBan, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let ban: ServerBanResponse = response.json().await?;
 
-        Ok(ban.ban)
+        Ok((ban.ban, meta))
+    }
+    /// Reason shown to moderators, at most [`MAX_BAN_REASON_LENGTH`] characters
+    pub fn reason(mut self, reason: &'a str) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+}
+
+/// Outcome of a [`BanUsersRequest`], in the order the bans completed
+#[derive(Debug, Default)]
+pub struct BanReport {
+    banned: Vec<ServerMemberBan>,
+    failed: Vec<(UserId, Error)>,
+}
+impl BanReport {
+    pub fn banned(&self) -> &[ServerMemberBan] {
+        &self.banned
+    }
+    /// Users that couldn't be banned, with the error the last attempt failed with
+    pub fn failed(&self) -> &[(UserId, Error)] {
+        &self.failed
+    }
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Bans several users at once, e.g. to clean up after a raid
+#[derive(Debug)]
+pub struct BanUsersRequest<'a> {
+    client: Http,
+    server: &'a ServerId,
+    users: &'a [UserId],
+    reason: Option<&'a str>,
+    concurrency: usize,
+}
+impl<'a> BanUsersRequest<'a> {
+    pub fn new(client: Http, server: &'a ServerId, users: &'a [UserId]) -> Self {
+        Self {
+            client,
+            server,
+            users,
+            reason: None,
+            concurrency: 4,
+        }
+    }
+    /// Bans every user, retrying those that hit the rate limit after the wait the server asks for.
+    ///
+    /// Failures don't stop the other bans; they're collected in the report.
+    pub async fn send(self) -> BanReport {
+        let permits = Arc::new(Semaphore::new(self.concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+        for user in self.users {
+            let client = self.client.clone();
+            let server = self.server.clone();
+            let user = user.clone();
+            let reason = self.reason.map(str::to_owned);
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let mut request = ServerBanRequest::new(client, &server, &user);
+                if let Some(reason) = &reason {
+                    request = request.reason(reason);
+                }
+                let result = request.send().await;
+                (user, result)
+            });
+        }
+        let mut report = BanReport::default();
+        while let Some(joined) = tasks.join_next().await {
+            // Tasks are never aborted and bans don't panic
+            let (user, result) = joined.expect("ban task failed");
+            match result {
+                Ok(ban) => report.banned.push(ban),
+                Err(e) => report.failed.push((user, e)),
+            }
+        }
+        report
     }
     pub fn reason(mut self, reason: &'a str) -> Self {
         self.reason = Some(reason);
         self
     }
+    /// How many bans are sent at the same time (default: 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,12 +213,12 @@ struct GetServerBanResponse {
 }
 #[derive(Debug)]
 pub struct GetServerBanRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
 }
 impl<'a> GetServerBanRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId) -> Self {
         Self {
             client,
             server,
@@ -93,45 +227,43 @@ impl<'a> GetServerBanRequest<'a> {
     }
     // TODO: change to option
     pub async fn send(self) -> Result<ServerMemberBan> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .get(format!(
-                "{API_BASE}/servers/{}/bans/{}",
-                self.server, self.user
-            ))
+            .get(Route::Ban {
+                server: self.server,
+                user: self.user,
+            })
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ServerMemberBan, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self
+            .client
+            .execute(request)
+            .await?
+            .check_status()
+            .map_err(|e| e.not_found("ban", self.user))?;
+        let meta = ResponseMeta::new(&response);
         let ban: GetServerBanResponse = response.json().await?;
 
-        Ok(ban.ban)
+        Ok((ban.ban, meta))
     }
 }
 
-#[derive(Debug)]
-pub struct DeleteServerBanRequest<'a> {
-    client: Client,
-    server: &'a ServerId,
-    user: &'a UserId,
-}
-impl<'a> DeleteServerBanRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId) -> Self {
-        Self {
-            client,
-            server,
-            user,
-        }
-    }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .delete(format!(
-                "{API_BASE}/servers/{}/bans/{}",
-                self.server, self.user
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
-        Ok(())
+request! {
+    pub struct DeleteServerBanRequest<'a> {
+        server: &'a ServerId,
+        user: &'a UserId,
     }
+    DELETE Ban => ()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -143,10 +275,10 @@ struct GetServerBansResponse {
 #[derive(Debug)]
 struct GetServerBansStream;
 impl GetServerBansStream {
-    fn iter(gsbr: GetServerBansRequest) -> impl Stream<Item = Result<ServerMemberBan>> + '_ {
+    fn iter(gsbr: GetServerBansRequest<'_>) -> impl Stream<Item = Result<ServerMemberBan>> + '_ {
         stream! {
-            let request = gsbr.client.get(format!("{API_BASE}/servers/{}/bans", gsbr.server)).build()?;
-            let response = gsbr.client.execute(request).await?.error_for_status()?;
+            let request = gsbr.client.get(Route::Bans { server: gsbr.server }).build()?;
+            let response = gsbr.client.execute(request).await?.check_status()?;
             let bans: GetServerBansResponse = response.json().await?;
 
             for ban in bans.bans {
@@ -155,16 +287,23 @@ impl GetServerBansStream {
         }
     }
 }
+/// Bans of a server, see [`GetServerBansRequest::send`]
+pub type ServerBansStream<'a> = ApiStream<'a, ServerMemberBan>;
+
 #[derive(Debug)]
 pub struct GetServerBansRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
 }
 impl<'a> GetServerBansRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId) -> Self {
+    pub fn new(client: Http, server: &'a ServerId) -> Self {
         Self { client, server }
     }
-    pub fn send(self) -> impl Stream<Item = Result<ServerMemberBan>> + 'a {
-        GetServerBansStream::iter(self)
+    pub fn send(self) -> ServerBansStream<'a> {
+        ApiStream::new(GetServerBansStream::iter(self))
+    }
+    /// Reads all the bans into a `Vec`, for when they aren't needed one at a time
+    pub async fn collect_all(self) -> Result<Vec<ServerMemberBan>> {
+        self.send().collect_all().await
     }
 }
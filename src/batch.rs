@@ -0,0 +1,89 @@
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::Poll;
+
+use crate::error::Result;
+
+type BoxedRequest<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Requests sent together, a few at a time, see [`GuildedClient::batch`](crate::GuildedClient::batch).
+///
+/// Requests are added as the future of their `send`, so any request fits as long as they share
+/// an output type; map the outputs to a common type (`()`, or an enum of them) to mix requests
+/// that return different models.
+pub struct Batch<'a, T = ()> {
+    requests: Vec<BoxedRequest<'a, T>>,
+    concurrency: usize,
+}
+impl<'a, T: Send + 'a> Batch<'a, T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            requests: Vec::new(),
+            concurrency: 4,
+        }
+    }
+    /// Adds a request, e.g. `client.assign_role(&server, &user, &role).send()`
+    pub fn push(mut self, request: impl Future<Output = Result<T>> + Send + 'a) -> Self {
+        self.requests.push(Box::pin(request));
+        self
+    }
+    /// How many requests are in flight at the same time (default: 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+    /// Sends the requests, returning their results in the order they were added.
+    ///
+    /// A failed request doesn't stop the others. Requests go through the client like any other,
+    /// so the throttling and ordering configured on it still apply, and when one is rate limited
+    /// the others wait along with it before it's retried, see [`Http::execute`](crate::http::Http::execute).
+    pub async fn send(self) -> Vec<Result<T>> {
+        let concurrency = self.concurrency.max(1);
+        let mut results: Vec<Option<Result<T>>> = self.requests.iter().map(|_| None).collect();
+        let mut waiting = self.requests.into_iter().enumerate();
+        let mut running: Vec<(usize, BoxedRequest<'a, T>)> = Vec::with_capacity(concurrency);
+        poll_fn(|cx| loop {
+            while running.len() < concurrency {
+                let Some(request) = waiting.next() else {
+                    break;
+                };
+                running.push(request);
+            }
+            if running.is_empty() {
+                return Poll::Ready(());
+            }
+            let before = running.len();
+            running.retain_mut(|(index, request)| match request.as_mut().poll(cx) {
+                Poll::Ready(result) => {
+                    results[*index] = Some(result);
+                    false
+                }
+                Poll::Pending => true,
+            });
+            // Only start more requests, and poll them, once some have finished
+            if running.len() == before {
+                return Poll::Pending;
+            }
+        })
+        .await;
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every request ran to completion"))
+            .collect()
+    }
+}
+impl<T> std::fmt::Debug for Batch<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Batch")
+            .field("requests", &self.requests.len())
+            .field("concurrency", &self.concurrency)
+            .finish()
+    }
+}
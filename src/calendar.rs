@@ -0,0 +1,163 @@
+use std::fmt::Display;
+use std::ops::Deref;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+#[cfg(feature = "chrono-tz")]
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+#[cfg(feature = "chrono-tz")]
+use chrono_tz::Tz;
+use reqwest::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::channel::ChannelId;
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
+use crate::member::UserId;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct CalendarEventId(u32);
+impl<'de> Deserialize<'de> for CalendarEventId {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        u32::deserialize(deserializer).map(Self)
+    }
+}
+impl Serialize for CalendarEventId {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+impl CalendarEventId {
+    pub fn new(event: u32) -> Self {
+        Self(event)
+    }
+}
+impl Deref for CalendarEventId {
+    type Target = u32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl Display for CalendarEventId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+impl PartialEq<u32> for CalendarEventId {
+    fn eq(&self, other: &u32) -> bool {
+        &self.0 == other
+    }
+}
+impl PartialEq<str> for CalendarEventId {
+    fn eq(&self, other: &str) -> bool {
+        let other: u32 = match other.parse() {
+            Ok(o) => o,
+            _ => return false,
+        };
+        self.0 == other
+    }
+}
+impl FromStr for CalendarEventId {
+    type Err = <u32 as FromStr>::Err;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        u32::from_str(s).map(Self)
+    }
+}
+
+api_enum! {
+    pub enum RsvpStatus {
+        Going = "going",
+        Maybe = "maybe",
+        Declined = "declined",
+        Invited = "invited",
+        Waitlisted = "waitlisted",
+        /// Invited, but hasn't answered yet
+        NotResponded = "not responded",
+    }
+}
+
+/// Sets the RSVP of several users to a calendar event in one call
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkRsvpRequest<'a> {
+    #[serde(skip)]
+    client: Http,
+    #[serde(skip)]
+    channel: &'a ChannelId,
+    #[serde(skip)]
+    event: &'a CalendarEventId,
+    user_ids: &'a [UserId],
+    status: RsvpStatus,
+}
+impl<'a> BulkRsvpRequest<'a> {
+    pub fn new(
+        client: Http,
+        channel: &'a ChannelId,
+        event: &'a CalendarEventId,
+        users: &'a [UserId],
+        status: RsvpStatus,
+    ) -> Self {
+        Self {
+            client,
+            channel,
+            event,
+            user_ids: users,
+            status,
+        }
+    }
+    pub async fn send(self) -> Result<()> {
+        self.send_with_meta().await.map(|_| ())
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        let request = self
+            .client
+            .put(Route::CalendarEventRsvps {
+                channel: self.channel,
+                event: self.event,
+            })
+            .json(self)
+            .build()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<ResponseMeta> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+
+        Ok(meta)
+    }
+}
+
+/// Converts a wall-clock time in a server's timezone to the UTC time events are stored in.
+///
+/// When clocks go back, a time that happens twice resolves to the first occurrence. Returns
+/// `None` for times skipped when clocks go forward (e.g. 02:30 on the day DST starts).
+#[cfg(feature = "chrono-tz")]
+pub fn local_to_utc(local: NaiveDateTime, timezone: Tz) -> Option<DateTime<Utc>> {
+    timezone
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Wall-clock time of a stored UTC time (e.g. an event's start or end) in a server's timezone
+#[cfg(feature = "chrono-tz")]
+pub fn utc_to_local(time: &DateTime<Utc>, timezone: Tz) -> DateTime<Tz> {
+    time.with_timezone(&timezone)
+}
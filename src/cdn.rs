@@ -0,0 +1,64 @@
+//! Variants of the images hosted on Guilded's CDN (avatars, banners, ...)
+
+use reqwest::Url;
+
+/// Size variant of an uploaded image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageSize {
+    Small,
+    Medium,
+    Large,
+}
+impl ImageSize {
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::Small => "Small",
+            Self::Medium => "Medium",
+            Self::Large => "Large",
+        }
+    }
+}
+
+/// Suffixes of the variants the CDN stores an uploaded image in
+const VARIANTS: [&str; 4] = ["Small", "Medium", "Large", "Hero"];
+
+/// URL of the `size` variant of an image on the CDN.
+///
+/// Uploaded images are named like `UserAvatar/{hash}-Large.png?w=450&h=450`; the size suffix is
+/// swapped and the `w`/`h` query, which only fits the original variant, is dropped. Default
+/// images (e.g. `asset/DefaultUserAvatars/profile_1.png`) only come in one size and are returned
+/// unchanged.
+pub fn resize(url: &Url, size: ImageSize) -> Url {
+    let Some(file) = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+    else {
+        return url.clone();
+    };
+    let (stem, extension) = file.rsplit_once('.').unwrap_or((file, ""));
+    let Some((hash, variant)) = stem.rsplit_once('-') else {
+        return url.clone();
+    };
+    if !VARIANTS.contains(&variant) {
+        return url.clone();
+    }
+    let mut resized = url.clone();
+    let renamed = match extension {
+        "" => format!("{hash}-{}", size.suffix()),
+        extension => format!("{hash}-{}.{extension}", size.suffix()),
+    };
+    if let Ok(mut segments) = resized.path_segments_mut() {
+        segments.pop().push(&renamed);
+    }
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| name != "w" && name != "h")
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if query.is_empty() {
+        resized.set_query(None);
+    } else {
+        resized.query_pairs_mut().clear().extend_pairs(query);
+    }
+    resized
+}
@@ -3,12 +3,19 @@ use std::ops::Deref;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
+use crate::dry_run::DryRun;
+use crate::error::{check_length, Constraint, ResponseExt, Result};
 use crate::groups::GroupId;
-use crate::API_BASE;
-use crate::{error::Result, member::UserId};
+use crate::http::Http;
+use crate::id;
+use crate::inflight::Inflight;
+use crate::member::UserId;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use uuid::Uuid;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
@@ -79,7 +86,7 @@ impl<'de> Deserialize<'de> for CategoryId {
     where
         D: serde::Deserializer<'de>,
     {
-        u32::deserialize(deserializer).map(Self)
+        id::deserialize_u32(deserializer).map(Self)
     }
 }
 impl Serialize for CategoryId {
@@ -129,19 +136,29 @@ impl FromStr for CategoryId {
     }
 }
 
+api_enum! {
+    pub enum ChannelType {
+        Announcements = "announcements",
+        Chat = "chat",
+        Calendar = "calendar",
+        Forums = "forums",
+        Media = "media",
+        Docs = "docs",
+        Voice = "voice",
+        List = "list",
+        Scheduling = "scheduling",
+        Stream = "stream",
+    }
+}
+
+/// Who can see a channel
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub enum ChannelType {
-    Announcements,
-    Chat,
-    Calendar,
-    Forums,
-    Media,
-    Docs,
-    Voice,
-    List,
-    Scheduling,
-    Stream,
+#[serde(rename_all = "lowercase")]
+pub enum ChannelVisibility {
+    /// Only visible to members mentioned in it and those with the permission to view private channels
+    Private,
+    /// Visible to everyone, including users who aren't members of the server
+    Public,
 }
 
 /// Information related to server channels
@@ -180,13 +197,16 @@ pub struct ServerChannel {
     /// Only relevant for server channels
     #[serde(rename = "categoryId")]
     category: Option<CategoryId>,
-    ///
+    /// The ID of the group
     #[serde(rename = "groupId")]
     group: GroupId,
-    /// Whether the channel can be accessed from users who are not members of the server (default: false)
+    /// Whether the channel can be accessed from users who are not members of the server (default: false).
+    /// Superseded by `visibility`, still sent for older channels
     #[serde(rename = "isPublic")]
     #[serde(default)]
     public: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<ChannelVisibility>,
     /// The ID of the user who archived this channel
     #[serde(rename = "archivedBy")]
     archived_by: Option<UserId>,
@@ -195,6 +215,67 @@ pub struct ServerChannel {
     archived_at: Option<DateTime<Utc>>,
 }
 
+impl ServerChannel {
+    /// Visibility of the channel, or `None` for a regular channel visible to server members.
+    ///
+    /// Falls back to the legacy `isPublic` flag for channels that don't report a visibility.
+    pub fn visibility(&self) -> Option<ChannelVisibility> {
+        self.visibility
+            .or(self.public.then_some(ChannelVisibility::Public))
+    }
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+    #[cfg(feature = "time")]
+    pub fn created_at_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created_at)
+    }
+    pub fn updated(&self) -> Option<&DateTime<Utc>> {
+        self.updated.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+    pub fn archived_at(&self) -> Option<&DateTime<Utc>> {
+        self.archived_at.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn archived_at_time(&self) -> Option<time::OffsetDateTime> {
+        self.archived_at
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+}
+
+/// Why a channel was rejected before sending, see [`Error::Channel`](crate::error::Error::Channel)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ChannelError {
+    /// The name is empty or longer than 100 characters
+    #[error("invalid channel name: {0}")]
+    InvalidName(Constraint),
+    /// The topic is longer than 512 characters
+    #[error("invalid channel topic: {0}")]
+    InvalidTopic(Constraint),
+}
+impl ChannelError {
+    /// Request field that was rejected, named like in [`Error::Validation`](crate::error::Error::Validation)
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::InvalidName(_) => "name",
+            Self::InvalidTopic(_) => "topic",
+        }
+    }
+    pub fn constraint(&self) -> Constraint {
+        match self {
+            Self::InvalidName(constraint) | Self::InvalidTopic(constraint) => *constraint,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct ServerChannelResponse {
@@ -208,7 +289,9 @@ pub struct CreateChannelRequest<'a> {
     topic: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "isPublic")]
-    public: Option<&'a str>,
+    public: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<ChannelVisibility>,
     #[serde(rename = "type")]
     channel_type: ChannelType,
     #[serde(rename = "serverId")]
@@ -220,16 +303,16 @@ pub struct CreateChannelRequest<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     category: Option<&'a CategoryId>,
     #[serde(skip)]
-    client: Client,
+    client: Http,
 }
 
-// TODO: ensure set fields follow all requirements from server
 impl<'a> CreateChannelRequest<'a> {
-    pub fn new(client: Client, server: &'a str, name: &'a str, channel_type: ChannelType) -> Self {
+    pub fn new(client: Http, server: &'a str, name: &'a str, channel_type: ChannelType) -> Self {
         Self {
             name,
             topic: None,
             public: None,
+            visibility: None,
             channel_type,
             server,
             group: None,
@@ -238,23 +321,50 @@ impl<'a> CreateChannelRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<ServerChannel> {
-        let request = self
-            .client
-            .post(format!("{API_BASE}/channels"))
-            .json(&self)
-            .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?; // TODO: actually make a proper error type
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        self.validate()?;
+        let request = self.client.post(Route::Channels).json(&self).build()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ServerChannel, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?; // TODO: actually make a proper error type
+        let meta = ResponseMeta::new(&response);
         let channel: ServerChannelResponse = response.json().await?;
-        Ok(channel.channel)
+        Ok((channel.channel, meta))
+    }
+    /// Checks the limits documented on [`ServerChannel`].
+    ///
+    /// Group and category aren't checked against each other yet, see `docs/API TODO.md`: a category
+    /// without a group is valid for categories of the server's home group, and which group a
+    /// category belongs to is only known to the server.
+    fn validate(&self) -> Result<()> {
+        check_length(self.name, 1, 100).map_err(ChannelError::InvalidName)?;
+        if let Some(topic) = self.topic {
+            check_length(topic, 0, 512).map_err(ChannelError::InvalidTopic)?;
+        }
+        Ok(())
     }
     pub fn topic(mut self, topic: &'a str) -> Self {
         self.topic = Some(topic);
         self
     }
-    pub fn public(mut self, public: &'a str) -> Self {
+    /// Whether the channel can be accessed by users who aren't members of the server
+    #[deprecated(note = "use `visibility(ChannelVisibility::Public)`")]
+    pub fn public(mut self, public: bool) -> Self {
         self.public = Some(public);
         self
     }
+    pub fn visibility(mut self, visibility: ChannelVisibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
     pub fn group(mut self, group: &'a GroupId) -> Self {
         self.group = Some(group);
         self
@@ -267,43 +377,137 @@ impl<'a> CreateChannelRequest<'a> {
 
 #[derive(Debug)]
 pub struct GetChannelRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
+    inflight: Option<Inflight>,
 }
 impl<'a> GetChannelRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId) -> Self {
-        Self { client, channel }
+    pub fn new(client: Http, channel: &'a ChannelId) -> Self {
+        Self {
+            client,
+            channel,
+            inflight: None,
+        }
     }
     pub async fn send(self) -> Result<ServerChannel> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .get(format!("{API_BASE}/channels/{}", self.channel))
+            .get(Route::Channel {
+                channel: self.channel,
+            })
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
-        let channel: ServerChannelResponse = response.json().await?;
 
-        Ok(channel.channel)
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ServerChannel, ResponseMeta)> {
+        let request = self.build_request()?;
+        let (body, meta) = match &self.inflight {
+            Some(inflight) => inflight
+                .execute(&self.client, request)
+                .await
+                .map_err(|e| e.not_found("channel", self.channel))?,
+            None => {
+                let response = self
+                    .client
+                    .execute(request)
+                    .await?
+                    .check_status()
+                    .map_err(|e| e.not_found("channel", self.channel))?;
+                let meta = ResponseMeta::new(&response);
+                (response.bytes().await?.to_vec(), meta)
+            }
+        };
+        let channel: ServerChannelResponse = serde_json::from_slice(&body)?;
+
+        Ok((channel.channel, meta))
+    }
+    pub(crate) fn inflight(mut self, inflight: Option<Inflight>) -> Self {
+        self.inflight = inflight;
+        self
     }
 }
 
-#[derive(Debug)]
-pub struct DeleteChannelRequest<'a> {
-    client: Client,
+#[derive(Debug, Serialize)]
+pub struct UpdateChannelRequest<'a> {
+    #[serde(skip)]
+    client: Http,
+    #[serde(skip)]
     channel: &'a ChannelId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<&'a str>,
+    /// `Some(None)` resets the channel to the default visibility
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<Option<ChannelVisibility>>,
 }
-impl<'a> DeleteChannelRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId) -> Self {
-        Self { client, channel }
+impl<'a> UpdateChannelRequest<'a> {
+    pub fn new(client: Http, channel: &'a ChannelId) -> Self {
+        Self {
+            client,
+            channel,
+            name: None,
+            topic: None,
+            visibility: None,
+        }
     }
-    pub async fn send(self) -> Result<()> {
+    pub async fn send(self) -> Result<ServerChannel> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        if let Some(name) = self.name {
+            check_length(name, 1, 100).map_err(ChannelError::InvalidName)?;
+        }
+        if let Some(topic) = self.topic {
+            check_length(topic, 0, 512).map_err(ChannelError::InvalidTopic)?;
+        }
         let request = self
             .client
-            .delete(format!("{API_BASE}/channels/{}", self.channel))
+            .patch(Route::Channel {
+                channel: self.channel,
+            })
+            .json(self)
             .build()?;
-        self.client.execute(request).await?.error_for_status()?;
 
-        Ok(())
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ServerChannel, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+        let channel: ServerChannelResponse = response.json().await?;
+
+        Ok((channel.channel, meta))
+    }
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+    pub fn topic(mut self, topic: &'a str) -> Self {
+        self.topic = Some(topic);
+        self
+    }
+    /// Sets the visibility; `None` makes it a regular channel visible to server members
+    pub fn visibility(mut self, visibility: Option<ChannelVisibility>) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
+}
+
+request! {
+    pub struct DeleteChannelRequest<'a> {
+        channel: &'a ChannelId,
     }
+    DELETE Channel => ()
 }
 
 pub struct GetChannelsRequest;
@@ -0,0 +1,29 @@
+//! Time source the client waits on, so rate limit and retry waits can be faked in tests
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use tokio::time::{self, Instant};
+
+/// Clock the client reads the time from and sleeps on, set with
+/// [`GuildedClientBuilder::clock`](crate::GuildedClientBuilder::clock)
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+}
+
+/// Tokio's timer, the default.
+///
+/// Follows `tokio::time::pause`, so in a test with paused time the waits complete as soon as
+/// the runtime has nothing else to do, without any real waiting.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(time::sleep(duration))
+    }
+}
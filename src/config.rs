@@ -0,0 +1,137 @@
+use std::env::{self, VarError};
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::routes::ApiVersion;
+use crate::{GuildedClientBuilder, TOKEN_VAR};
+
+/// Settings of a [`GuildedClient`](crate::GuildedClient), deserialized from a configuration file.
+///
+/// Every field is optional; durations are in milliseconds. In TOML:
+///
+/// ```toml
+/// api_version = "v1"
+/// timeout_ms = 10000
+/// token = { file = "/run/secrets/guilded_token" }
+///
+/// [rate_limit]
+/// retries = 5
+/// ordered_sends = true
+/// throttle = { max = 5, period_ms = 5000, coalesce = true }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GuildedConfig {
+    token: TokenSource,
+    base_url: Option<String>,
+    api_version: ApiVersion,
+    timeout_ms: Option<u64>,
+    connect_timeout_ms: Option<u64>,
+    coalesce_requests: bool,
+    rate_limit: RateLimitConfig,
+}
+impl GuildedConfig {
+    /// Builder with these settings, to add those that can't come from a file (audit log, metrics, ...)
+    pub fn builder(&self) -> Result<GuildedClientBuilder> {
+        let mut builder = GuildedClientBuilder::new(&self.token.resolve()?)
+            .api_version(self.api_version.clone())
+            .coalesce_requests(self.coalesce_requests);
+        if let Some(base_url) = &self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(timeout) = self.timeout_ms {
+            builder = builder.timeout(Duration::from_millis(timeout));
+        }
+        if let Some(timeout) = self.connect_timeout_ms {
+            builder = builder.connect_timeout(Duration::from_millis(timeout));
+        }
+
+        Ok(self.rate_limit.apply(builder))
+    }
+}
+
+/// Where the bot token is read from (default: the `GUILDED_TOKEN` environment variable)
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenSource {
+    /// The token itself
+    Value(String),
+    /// Name of an environment variable holding the token
+    Env(String),
+    /// File holding the token, e.g. a mounted secret
+    File(PathBuf),
+}
+impl TokenSource {
+    fn resolve(&self) -> Result<String> {
+        match self {
+            Self::Value(token) => Ok(token.trim().to_owned()),
+            Self::Env(var) => match env::var(var) {
+                Ok(token) => Ok(token.trim().to_owned()),
+                Err(VarError::NotPresent) => Err(Error::MissingToken(var.clone())),
+                Err(VarError::NotUnicode(_)) => {
+                    Err(Error::InvalidToken("token is not valid unicode"))
+                }
+            },
+            Self::File(path) => Ok(fs::read_to_string(path)?.trim().to_owned()),
+        }
+    }
+}
+impl Default for TokenSource {
+    fn default() -> Self {
+        Self::Env(TOKEN_VAR.to_owned())
+    }
+}
+impl Debug for TokenSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Value(_) => f.debug_tuple("Value").field(&"<redacted>").finish(),
+            Self::Env(var) => f.debug_tuple("Env").field(var).finish(),
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+        }
+    }
+}
+
+/// How the client deals with rate limits, see [`GuildedConfig`]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// How many times a rate limited request is retried
+    retries: Option<u32>,
+    /// Wait before retrying when the server doesn't say how long to wait
+    retry_after_ms: Option<u64>,
+    ordered_sends: bool,
+    throttle: Option<ThrottleConfig>,
+}
+impl RateLimitConfig {
+    fn apply(&self, mut builder: GuildedClientBuilder) -> GuildedClientBuilder {
+        if let Some(retries) = self.retries {
+            builder = builder.rate_limit_retries(retries);
+        }
+        if let Some(wait) = self.retry_after_ms {
+            builder = builder.default_retry_after(Duration::from_millis(wait));
+        }
+        if let Some(throttle) = &self.throttle {
+            builder = builder.throttle_sends(
+                throttle.max,
+                Duration::from_millis(throttle.period_ms),
+                throttle.coalesce,
+            );
+        }
+        builder.ordered_sends(self.ordered_sends)
+    }
+}
+
+/// Settings of [`GuildedClientBuilder::throttle_sends`]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThrottleConfig {
+    max: usize,
+    period_ms: u64,
+    #[serde(default)]
+    coalesce: bool,
+}
@@ -0,0 +1,80 @@
+use reqwest::header::{self, HeaderMap};
+use reqwest::{Request, Response};
+
+use crate::TOKEN_PREFIX;
+
+/// Logs the headers and body of a request about to be sent
+pub(crate) fn log_request(request: &Request) {
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(String::from_utf8_lossy)
+        .unwrap_or_default();
+    tracing::trace!(
+        method = %request.method(),
+        url = %request.url(),
+        headers = %redact(&headers(request.headers())),
+        body = %redact(&body),
+        "sending request"
+    );
+}
+
+/// Reads and logs the body of a response, handing back an equivalent response to parse it from
+pub(crate) async fn log_response(response: Response) -> reqwest::Result<Response> {
+    let status = response.status();
+    let url = response.url().clone();
+    let mut builder = ::http::Response::builder()
+        .status(status)
+        .version(response.version());
+    for (name, value) in response.headers() {
+        // The body below is already decoded
+        if name != header::CONTENT_ENCODING && name != header::CONTENT_LENGTH {
+            builder = builder.header(name, value);
+        }
+    }
+    let body = response.bytes().await?;
+    tracing::trace!(
+        %status,
+        %url,
+        body = %redact(&String::from_utf8_lossy(&body)),
+        "received response"
+    );
+    // Loses the URL, which is why `Http::execute` keeps it in an `Origin` afterwards
+    let rebuilt = builder
+        .body(body)
+        .expect("parts were taken from a valid response");
+
+    Ok(rebuilt.into())
+}
+
+fn headers(headers: &HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if name == header::AUTHORIZATION {
+                "[redacted]"
+            } else {
+                value.to_str().unwrap_or("[binary]")
+            };
+            format!("{name}: {value}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Replaces anything that looks like a bot token with `[redacted]`
+fn redact(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(TOKEN_PREFIX) {
+        redacted.push_str(&rest[..start]);
+        redacted.push_str("[redacted]");
+        rest = rest[start..]
+            .trim_start_matches(TOKEN_PREFIX)
+            .trim_start_matches(|c: char| {
+                c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=' | '-' | '_')
+            });
+    }
+    redacted.push_str(rest);
+    redacted
+}
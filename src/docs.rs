@@ -6,14 +6,19 @@ use std::str::FromStr;
 
 use async_stream::stream;
 use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
-use reqwest::{Client, Url};
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
 use tokio_stream::Stream;
 
 use crate::channel::ChannelId;
-use crate::error::Result;
+use crate::dry_run::DryRun;
+use crate::error::{Constraint, Error, ResponseExt, Result};
+use crate::http::Http;
+use crate::id;
 use crate::member::{ServerId, UserId};
-use crate::API_BASE;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
+use crate::streams::{ApiStream, Page};
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 #[repr(transparent)]
@@ -23,7 +28,7 @@ impl<'de> Deserialize<'de> for DocId {
     where
         D: serde::Deserializer<'de>,
     {
-        u32::deserialize(deserializer).map(Self)
+        id::deserialize_u32(deserializer).map(Self)
     }
 }
 impl Serialize for DocId {
@@ -94,6 +99,24 @@ pub struct Doc {
     #[serde(skip_serializing_if = "Option::is_none")]
     updated_by: Option<UserId>,
 }
+impl Doc {
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+    pub fn updated(&self) -> Option<&DateTime<Utc>> {
+        self.updated.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -112,13 +135,13 @@ impl<'a> CreateDocBody<'a> {
 }
 #[derive(Debug)]
 pub struct CreateDocRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     title: &'a str,
     content: &'a str,
 }
 impl<'a> CreateDocRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, title: &'a str, content: &'a str) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, title: &'a str, content: &'a str) -> Self {
         Self {
             client,
             channel,
@@ -127,16 +150,30 @@ impl<'a> CreateDocRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<Doc> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let body = CreateDocBody::new(self.title, self.content);
         let request = self
             .client
-            .post(format!("{API_BASE}/channels/{}/docs", self.channel))
+            .post(Route::Docs {
+                channel: self.channel,
+            })
             .json(&body)
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(Doc, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let doc: CreateDocResponse = response.json().await?;
 
-        Ok(doc.doc)
+        Ok((doc.doc, meta))
     }
 }
 
@@ -146,82 +183,128 @@ struct GetDocsResponse {
     docs: Vec<Doc>,
 }
 #[derive(Debug)]
-enum DocsStream<'a> {
+enum DocsPages<'a> {
     Uninitialized(GetDocsRequest<'a>),
     Iterating {
-        client: Client,
+        client: Http,
         channel: &'a ChannelId,
+        after: Option<DateTime<Utc>>,
+        title: Option<String>,
         docs: Vec<Doc>,
     },
     Transition,
 }
-impl<'a> DocsStream<'a> {
-    pub fn iter(gdr: GetDocsRequest) -> impl Stream<Item = Result<Doc>> + '_ {
+impl<'a> DocsPages<'a> {
+    pub fn iter(gdr: GetDocsRequest<'_>) -> impl Stream<Item = Result<Doc>> + '_ {
         stream! {
-            let mut state = DocsStream::Uninitialized(gdr);
+            let mut state = DocsPages::Uninitialized(gdr);
 
             loop {
-                match mem::replace(&mut state, DocsStream::Transition) {
-                    DocsStream::Uninitialized(request) => {
+                match mem::replace(&mut state, DocsPages::Transition) {
+                    DocsPages::Uninitialized(request) => {
                         let client = request.client.clone();
                         let channel = request.channel;
+                        let after = request.after;
+                        let title = request.title.clone();
                         let docs = request.send_part().await?;
-                        state = DocsStream::Iterating { client, channel, docs };
+                        state = DocsPages::Iterating { client, channel, after, title, docs };
                         continue;
                     }
-                    DocsStream::Iterating {client, channel, docs } => {
+                    DocsPages::Iterating {client, channel, after, title, docs } => {
                         let mut last_doc = None;
                         for doc in docs {
+                            // Docs arrive newest first, so nothing past this point is in range
+                            if after.is_some_and(|after| doc.created <= after) {
+                                return;
+                            }
                             last_doc = Some(doc.created);
+                            if title.as_ref().is_some_and(|title| !doc.title.to_lowercase().contains(title)) {
+                                continue;
+                            }
                             yield Ok(doc);
                         }
                         if let Some(last_doc) = last_doc {
-                            let request = GetDocsRequest::new(client, channel).before(last_doc);
-                            state = DocsStream::Uninitialized(request);
+                            let mut request = GetDocsRequest::new(client, channel).before(last_doc);
+                            request.after = after;
+                            request.title = title;
+                            state = DocsPages::Uninitialized(request);
                             continue;
                         }
                         break;
                     }
-                    DocsStream::Transition => unreachable!("Invariant broken: stream began processing on a state transition"),
+                    DocsPages::Transition => unreachable!("Invariant broken: stream began processing on a state transition"),
                 }
             }
         }
     }
 }
+/// Docs of a channel, see [`GetDocsRequest::send`]
+pub type DocsStream<'a> = ApiStream<'a, Doc>;
+
 #[derive(Debug)]
 pub struct GetDocsRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     before: Option<String>,
+    after: Option<DateTime<Utc>>,
+    title: Option<String>,
     limit: Option<u32>,
 }
 impl<'a> GetDocsRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId) -> Self {
         Self {
             client,
             channel,
             before: None,
+            after: None,
+            title: None,
             limit: None,
         }
     }
-    pub fn send(self) -> impl Stream<Item = Result<Doc>> + 'a {
-        DocsStream::iter(self)
+    pub fn send(self) -> DocsStream<'a> {
+        ApiStream::new(DocsPages::iter(self))
+    }
+    /// Reads all the docs into a `Vec`, for when they aren't needed one at a time
+    pub async fn collect_all(self) -> Result<Vec<Doc>> {
+        self.send().collect_all().await
+    }
+    /// Fetches a single page of docs, newest first, for callers that paginate themselves.
+    ///
+    /// Passing [`Page::next`] to [`before`](Self::before) fetches the page after it. The title
+    /// filter can leave a page with fewer docs, or none, while there are more pages.
+    pub async fn send_page(self) -> Result<Page<Doc>> {
+        let after = self.after;
+        let title = self.title.clone();
+        let mut docs = self.send_part().await?;
+        let end = after.and_then(|after| docs.iter().position(|doc| doc.created <= after));
+        let next = match end {
+            Some(end) => {
+                docs.truncate(end);
+                None
+            }
+            None => docs.last().map(|doc| doc.created),
+        };
+        if let Some(title) = title {
+            docs.retain(|doc| doc.title.to_lowercase().contains(&title));
+        }
+        Ok(Page::new(docs, next))
     }
     async fn send_part(self) -> Result<Vec<Doc>> {
-        let mut url: Url = format!("{API_BASE}/channels/{}/docs", self.channel)
-            .parse()
-            .unwrap();
-        if let Some(before) = self.before {
-            url.set_query(Some(&format!("before={before}&")));
+        let mut query = Vec::new();
+        if let Some(before) = &self.before {
+            query.push(("before", before.clone()));
         }
         if let Some(limit) = self.limit {
-            url.set_query(Some(&format!(
-                "{}limit={limit}&",
-                url.query().unwrap_or_default()
-            )))
+            query.push(("limit", limit.to_string()));
         }
-        let request = self.client.get(url).build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+        let request = self
+            .client
+            .get(Route::Docs {
+                channel: self.channel,
+            })
+            .query(&query)
+            .build()?;
+        let response = self.client.execute(request).await?.check_status()?;
         let docs: GetDocsResponse = response.json().await?;
         Ok(docs.docs)
     }
@@ -230,6 +313,16 @@ impl<'a> GetDocsRequest<'a> {
         self.before = Some(before.to_rfc3339_opts(SecondsFormat::Millis, true));
         self
     }
+    /// Only returns docs created after this time; the stream ends once it is reached
+    pub fn after<T: TimeZone>(mut self, after: DateTime<T>) -> Self {
+        self.after = Some(after.with_timezone(&Utc));
+        self
+    }
+    /// Only returns docs whose title contains `title`, ignoring case. Filtered client-side.
+    pub fn title_contains(mut self, title: &str) -> Self {
+        self.title = Some(title.to_lowercase());
+        self
+    }
     //pub fn limit(mut self, limit: u32) -> Self {
     //    // TODO: Check the limit
     //    self.limit = Some(limit);
@@ -244,12 +337,12 @@ struct GetDocResponse {
 }
 #[derive(Debug)]
 pub struct GetDocRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     doc: &'a DocId,
 }
 impl<'a> GetDocRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, doc: &'a DocId) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, doc: &'a DocId) -> Self {
         Self {
             client,
             channel,
@@ -257,17 +350,34 @@ impl<'a> GetDocRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<Doc> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .get(format!(
-                "{API_BASE}/channels/{}/docs/{}",
-                self.channel, self.doc
-            ))
+            .get(Route::Doc {
+                channel: self.channel,
+                doc: self.doc,
+            })
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(Doc, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self
+            .client
+            .execute(request)
+            .await?
+            .check_status()
+            .map_err(|e| e.not_found("doc", self.doc))?;
+        let meta = ResponseMeta::new(&response);
         let doc: GetDocResponse = response.json().await?;
 
-        Ok(doc.doc)
+        Ok((doc.doc, meta))
     }
 }
 
@@ -277,81 +387,115 @@ struct UpdateDocResponse {
 }
 #[derive(Debug, Serialize)]
 struct UpdateDocBody<'a> {
-    title: &'a str,
-    content: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<&'a str>,
 }
 impl<'a> UpdateDocBody<'a> {
-    pub fn new(title: &'a str, content: &'a str) -> Self {
+    pub fn new(title: Option<&'a str>, content: Option<&'a str>) -> Self {
         Self { title, content }
     }
 }
 
+/// Updates a doc's title, content, or both; at least one has to be set, or sending fails with an
+/// [`Error::Validation`] for the `title_or_content` field.
+///
+/// The API replaces both fields at once, so when only one is set [`send`](Self::send) fetches
+/// the doc first and resends the half that isn't changing. Setting both skips the fetch.
 #[derive(Debug)]
 pub struct UpdateDocRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     doc: &'a DocId,
-    // TODO: optional?
-    title: &'a str,
-    // TODO: optional?
-    content: &'a str,
+    title: Option<&'a str>,
+    content: Option<&'a str>,
 }
 impl<'a> UpdateDocRequest<'a> {
-    pub fn new(
-        client: Client,
-        channel: &'a ChannelId,
-        doc: &'a DocId,
-        title: &'a str,
-        content: &'a str,
-    ) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, doc: &'a DocId) -> Self {
         Self {
             client,
             channel,
             doc,
-            title,
-            content,
+            title: None,
+            content: None,
         }
     }
     pub async fn send(self) -> Result<Doc> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    /// Shows the request as built from the fields that were set, without the fetched half
+    pub fn dry_run(&self) -> Result<DryRun> {
         let body = UpdateDocBody::new(self.title, self.content);
+        self.build_request(&body)
+            .map(|request| DryRun::new(&request))
+    }
+    /// Fails when there's nothing to update
+    fn validate(&self) -> Result<()> {
+        if self.title.is_none() && self.content.is_none() {
+            return Err(Error::Validation {
+                field: "title_or_content",
+                constraint: Constraint::Required,
+                value: String::new(),
+            });
+        }
+        Ok(())
+    }
+    fn build_request(&self, body: &UpdateDocBody<'_>) -> Result<Request> {
+        self.validate()?;
         let request = self
             .client
-            .put(format!(
-                "{API_BASE}/channels/{}/docs/{}",
-                self.channel, self.doc
-            ))
-            .json(&body)
+            .put(Route::Doc {
+                channel: self.channel,
+                doc: self.doc,
+            })
+            .json(body)
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(Doc, ResponseMeta)> {
+        // Checked before the doc is fetched, so an empty update doesn't make any request
+        self.validate()?;
+        let current = match (self.title, self.content) {
+            (Some(_), Some(_)) => None,
+            _ => Some(
+                GetDocRequest::new(self.client.clone(), self.channel, self.doc)
+                    .send()
+                    .await?,
+            ),
+        };
+        let body = UpdateDocBody::new(
+            self.title
+                .or(current.as_ref().map(|doc| doc.title.as_str())),
+            self.content
+                .or(current.as_ref().map(|doc| doc.content.as_str())),
+        );
+        let request = self.build_request(&body)?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let doc: UpdateDocResponse = response.json().await?;
 
-        Ok(doc.doc)
+        Ok((doc.doc, meta))
+    }
+    /// Sets the title. Without [`content`](Self::content) too, the current content is fetched
+    /// and sent back, so an edit made to it in between is overwritten.
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = Some(title);
+        self
+    }
+    /// Sets the content. Without [`title`](Self::title) too, the current title is fetched and
+    /// sent back, so an edit made to it in between is overwritten.
+    pub fn content(mut self, content: &'a str) -> Self {
+        self.content = Some(content);
+        self
     }
 }
 
-#[derive(Debug)]
-pub struct DeleteDocRequest<'a> {
-    client: Client,
-    channel: &'a ChannelId,
-    doc: &'a DocId,
-}
-impl<'a> DeleteDocRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, doc: &'a DocId) -> Self {
-        Self {
-            client,
-            channel,
-            doc,
-        }
-    }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .delete(format!(
-                "{API_BASE}/channels/{}/docs/{}",
-                self.channel, self.doc
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?;
-        Ok(())
+request! {
+    pub struct DeleteDocRequest<'a> {
+        channel: &'a ChannelId,
+        doc: &'a DocId,
     }
+    DELETE Doc => ()
 }
@@ -0,0 +1,47 @@
+use std::fmt::{self, Display};
+
+use reqwest::{Method, Request, Url};
+
+/// Request that would have been sent, as returned by the `dry_run` method of request builders.
+///
+/// `dry_run` builds the request the same way `send` does, so it fails with the same validation
+/// errors. Only what depends on the API is left out, which the request's `dry_run` docs point out
+/// (e.g. the half of a doc that `UpdateDocRequest` fetches).
+#[derive(Debug, Clone)]
+pub struct DryRun {
+    method: Method,
+    url: Url,
+    body: Option<String>,
+}
+impl DryRun {
+    pub(crate) fn new(request: &Request) -> Self {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+        Self {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            body,
+        }
+    }
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+    /// Serialized body, if the request has one that isn't streamed (multipart uploads are)
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+}
+impl Display for DryRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        if let Some(body) = &self.body {
+            write!(f, " {body}")?;
+        }
+        Ok(())
+    }
+}
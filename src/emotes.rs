@@ -0,0 +1,111 @@
+//! Lookup of [`EmoteId`]s by shortcode
+
+use std::collections::HashMap;
+
+use crate::reactions::EmoteId;
+
+/// Guilded's built-in emotes seeded by [`EmoteRegistry::with_defaults`], by shortcode.
+///
+/// Only holds IDs confirmed against what the API sends; the rest of the stock set is added as
+/// each one is confirmed.
+const DEFAULT_EMOTES: &[(&str, u32)] = &[("grinning", 90002569)];
+
+/// 😀 `:grinning:`
+pub const fn grinning() -> EmoteId {
+    EmoteId::new(90002569)
+}
+
+/// Maps shortcodes such as `:grinning:` to emote IDs, so code doesn't hardcode the numbers.
+///
+/// Shortcodes are matched with or without the surrounding colons and regardless of case.
+#[derive(Debug, Clone, Default)]
+pub struct EmoteRegistry {
+    emotes: HashMap<String, EmoteId>,
+    /// Reverse of `emotes`, for [`EmoteRegistry::shortcode`]
+    shortcodes: HashMap<EmoteId, String>,
+}
+impl EmoteRegistry {
+    /// Empty registry, see [`EmoteRegistry::with_defaults`] for one with Guilded's emotes
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Registry with Guilded's built-in emotes, to add the server's custom emotes to.
+    ///
+    /// Only the built-in emotes whose IDs are confirmed are seeded so far, see [`grinning`];
+    /// register others, e.g. `:white_check_mark:`, with [`EmoteRegistry::insert`] until they are.
+    pub fn with_defaults() -> Self {
+        DEFAULT_EMOTES
+            .iter()
+            .map(|&(shortcode, id)| (shortcode, EmoteId::new(id)))
+            .collect()
+    }
+    /// Adds an emote, replacing any emote previously registered under `shortcode`
+    pub fn insert(&mut self, shortcode: &str, emote: EmoteId) -> Option<EmoteId> {
+        let shortcode = normalize(shortcode);
+        let previous = self.emotes.insert(shortcode.clone(), emote);
+        if let Some(previous) = previous {
+            self.unlink(&previous, &shortcode);
+        }
+        self.shortcodes.insert(emote, shortcode);
+        previous
+    }
+    /// Builder-style [`EmoteRegistry::insert`]
+    pub fn with(mut self, shortcode: &str, emote: EmoteId) -> Self {
+        self.insert(shortcode, emote);
+        self
+    }
+    pub fn get(&self, shortcode: &str) -> Option<&EmoteId> {
+        self.emotes.get(&normalize(shortcode))
+    }
+    pub fn remove(&mut self, shortcode: &str) -> Option<EmoteId> {
+        let shortcode = normalize(shortcode);
+        let removed = self.emotes.remove(&shortcode)?;
+        self.unlink(&removed, &shortcode);
+        Some(removed)
+    }
+    /// Shortcode an emote is registered under, e.g. to show a reaction by name.
+    ///
+    /// For an emote registered under several shortcodes, this is the one added last.
+    pub fn shortcode(&self, emote: &EmoteId) -> Option<&str> {
+        self.shortcodes.get(emote).map(String::as_str)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &EmoteId)> {
+        self.emotes
+            .iter()
+            .map(|(shortcode, emote)| (shortcode.as_str(), emote))
+    }
+    /// Points the reverse lookup of `emote` away from `shortcode`, which no longer names it
+    fn unlink(&mut self, emote: &EmoteId, shortcode: &str) {
+        if self.shortcodes.get(emote).map(String::as_str) != Some(shortcode) {
+            return;
+        }
+        // Fall back to another shortcode of the same emote, if it has one
+        match self.emotes.iter().find(|(_, id)| *id == emote) {
+            Some((other, _)) => self.shortcodes.insert(*emote, other.clone()),
+            None => self.shortcodes.remove(emote),
+        };
+    }
+}
+impl<'a> FromIterator<(&'a str, EmoteId)> for EmoteRegistry {
+    fn from_iter<T: IntoIterator<Item = (&'a str, EmoteId)>>(iter: T) -> Self {
+        let mut registry = Self::new();
+        for (shortcode, emote) in iter {
+            registry.insert(shortcode, emote);
+        }
+        registry
+    }
+}
+impl Extend<(String, EmoteId)> for EmoteRegistry {
+    fn extend<T: IntoIterator<Item = (String, EmoteId)>>(&mut self, iter: T) {
+        for (shortcode, emote) in iter {
+            self.insert(&shortcode, emote);
+        }
+    }
+}
+
+fn normalize(shortcode: &str) -> String {
+    let shortcode = shortcode.trim();
+    let shortcode = shortcode.strip_prefix(':').unwrap_or(shortcode);
+    let shortcode = shortcode.strip_suffix(':').unwrap_or(shortcode);
+    shortcode.to_lowercase()
+}
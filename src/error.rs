@@ -1,6 +1,17 @@
+use std::fmt::Display;
+use std::result::Result as StdResult;
+use std::time::Duration;
+
+use reqwest::{Method, Response, StatusCode};
 use thiserror::Error;
 
-pub type Result<T> = std::result::Result<T, Error>;
+use crate::channel::ChannelError;
+use crate::http::Origin;
+use crate::message::MessageError;
+use crate::meta::ResponseMeta;
+use crate::routes::template;
+
+pub type Result<T> = StdResult<T, Error>;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -8,4 +19,185 @@ pub enum Error {
     ReqwestError(#[from] reqwest::Error),
     #[error("{0}")]
     JsonError(#[from] serde_json::Error),
+    #[error("{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("{0} is not set")]
+    MissingToken(String),
+    #[error("invalid token: {0}")]
+    InvalidToken(&'static str),
+    #[error("unauthorized, the bot token is invalid or has expired: {0}")]
+    Unauthorized(RequestContext),
+    #[error("{0}")]
+    Message(#[from] MessageError),
+    #[error("{0}")]
+    Channel(#[from] ChannelError),
+    /// A field without a dedicated error was rejected before sending, as the API would reject it too
+    #[error("invalid {field}: {constraint}")]
+    Validation {
+        field: &'static str,
+        constraint: Constraint,
+        value: String,
+    },
+    /// The API answered with an error status not covered by a more specific variant
+    #[error("{0}")]
+    Status(RequestContext),
+    /// The requested resource doesn't exist, or the bot can't see it.
+    ///
+    /// Holds the 404's request, unless the resource was missing from a response that succeeded.
+    #[error("{resource} {id} not found")]
+    NotFound {
+        resource: &'static str,
+        id: String,
+        context: Option<RequestContext>,
+    },
+    /// Too many requests, even after the retries; holds how long the server asked to wait, if it said
+    #[error("rate limited: {context}")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        context: RequestContext,
+    },
+    #[cfg(feature = "cron")]
+    #[error("{0}")]
+    CronError(#[from] cron::error::Error),
+}
+
+impl Error {
+    /// HTTP status the request failed with, if it got a response
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::ReqwestError(e) => e.status(),
+            Self::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+            e => e.context().map(RequestContext::status),
+        }
+    }
+    /// Request that failed, for every error the API answered with an error status
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Unauthorized(context)
+            | Self::Status(context)
+            | Self::RateLimited { context, .. } => Some(context),
+            Self::NotFound { context, .. } => context.as_ref(),
+            _ => None,
+        }
+    }
+    /// Turns a 404 from fetching `resource` into [`Error::NotFound`]
+    pub(crate) fn not_found(self, resource: &'static str, id: &impl Display) -> Self {
+        match self {
+            Self::Status(context) if context.status == StatusCode::NOT_FOUND => Self::NotFound {
+                resource,
+                id: id.to_string(),
+                context: Some(context),
+            },
+            e => e,
+        }
+    }
+}
+
+/// Which request an error came from, see [`Error::context`].
+///
+/// Only holds the route template (e.g. `/channels/:id/messages`), never the headers, so it's
+/// safe to log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    method: Option<Method>,
+    route: String,
+    status: StatusCode,
+    request_id: Option<String>,
+}
+impl RequestContext {
+    fn new(response: &Response) -> Self {
+        let origin = response.extensions().get::<Origin>();
+        Self {
+            method: origin.map(|origin| origin.method.clone()),
+            route: template(origin.map_or(response.url(), |origin| &origin.url)),
+            status: response.status(),
+            request_id: ResponseMeta::new(response).request_id().map(str::to_owned),
+        }
+    }
+    /// Method of the request, if it was sent through [`Http::execute`](crate::http::Http::execute)
+    pub fn method(&self) -> Option<&Method> {
+        self.method.as_ref()
+    }
+    /// Path of the request with the IDs replaced by `:id`
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+    /// ID the server assigned to the request, useful when reporting issues
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+}
+impl Display for RequestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(method) = &self.method {
+            write!(f, "{method} ")?;
+        }
+        write!(f, "{} failed with {}", self.route, self.status)?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request {request_id})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Rule a field broke, see [`Error::Validation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Constraint {
+    /// Needs at least this many characters
+    MinLength(usize),
+    /// Allows at most this many characters
+    MaxLength(usize),
+    /// Has to be set
+    Required,
+}
+impl Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MinLength(1) => write!(f, "must not be empty"),
+            Self::MinLength(min) => write!(f, "must be at least {min} characters"),
+            Self::MaxLength(max) => write!(f, "must be at most {max} characters"),
+            Self::Required => write!(f, "must be set"),
+        }
+    }
+}
+
+/// Checks that `value` has between `min` and `max` characters, returning the limit it broke
+pub(crate) fn check_length(value: &str, min: usize, max: usize) -> StdResult<(), Constraint> {
+    let length = value.chars().count();
+    if length < min {
+        Err(Constraint::MinLength(min))
+    } else if length > max {
+        Err(Constraint::MaxLength(max))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) trait ResponseExt: Sized {
+    /// Like [`Response::error_for_status`], but maps statuses with a dedicated [`Error`] variant
+    fn check_status(self) -> Result<Self>;
+    fn check_status_ref(&self) -> Result<&Self>;
+}
+impl ResponseExt for Response {
+    fn check_status(self) -> Result<Self> {
+        self.check_status_ref()?;
+        Ok(self)
+    }
+    fn check_status_ref(&self) -> Result<&Self> {
+        match self.status() {
+            StatusCode::UNAUTHORIZED => Err(Error::Unauthorized(RequestContext::new(self))),
+            StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited {
+                retry_after: ResponseMeta::new(self).retry_after(),
+                context: RequestContext::new(self),
+            }),
+            status if status.is_client_error() || status.is_server_error() => {
+                Err(Error::Status(RequestContext::new(self)))
+            }
+            _ => Ok(self),
+        }
+    }
 }
@@ -0,0 +1,352 @@
+//! Payloads of the events sent over the gateway, named after their event type
+
+use std::collections::HashSet;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::channel::ServerChannel;
+#[cfg(feature = "docs")]
+use crate::docs::Doc;
+use crate::error::Result;
+#[cfg(feature = "forums")]
+use crate::forums::ForumThread;
+#[cfg(feature = "list")]
+use crate::list::ListItem;
+use crate::member::{ServerId, UserId};
+use crate::reactions::MessageReaction;
+use crate::roles::RoleId;
+use crate::server::Server;
+
+/// Defines the payload of an event carrying a single model, along with the server it happened in
+macro_rules! server_event {
+    ($(#[$meta:meta])* $name:ident { $field:ident: $model:ty }) => {
+        $(#[$meta])*
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[serde(rename_all = "camelCase")]
+        pub struct $name {
+            #[serde(rename = "serverId")]
+            server: ServerId,
+            $field: $model,
+        }
+        impl $name {
+            pub fn server(&self) -> &ServerId {
+                &self.server
+            }
+            pub fn $field(&self) -> &$model {
+                &self.$field
+            }
+            /// Takes the model out of the event
+            pub fn into_inner(self) -> $model {
+                self.$field
+            }
+        }
+    };
+}
+
+/// Kind of a gateway frame
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+#[non_exhaustive]
+pub enum Opcode {
+    /// An event, see [`RawFrame::event_type`]
+    Event,
+    /// First frame of a connection, with the heartbeat interval
+    Welcome,
+    /// Missed events were replayed after reconnecting
+    Resume,
+    /// Opcode this version of the crate doesn't know about
+    Unknown(u8),
+}
+impl From<u8> for Opcode {
+    fn from(op: u8) -> Self {
+        match op {
+            0 => Self::Event,
+            1 => Self::Welcome,
+            2 => Self::Resume,
+            op => Self::Unknown(op),
+        }
+    }
+}
+impl From<Opcode> for u8 {
+    fn from(op: Opcode) -> Self {
+        match op {
+            Opcode::Event => 0,
+            Opcode::Welcome => 1,
+            Opcode::Resume => 2,
+            Opcode::Unknown(op) => op,
+        }
+    }
+}
+
+/// Gateway frame as received, for events and opcodes without a typed payload yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawFrame {
+    op: Opcode,
+    #[serde(rename = "t")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_type: Option<String>,
+    #[serde(rename = "d")]
+    #[serde(default)]
+    data: Value,
+    /// ID to resume from after reconnecting
+    #[serde(rename = "s")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message_id: Option<String>,
+}
+impl RawFrame {
+    pub fn op(&self) -> Opcode {
+        self.op
+    }
+    /// Name of the event, e.g. `ChatMessageCreated`, for [`Opcode::Event`] frames
+    pub fn event_type(&self) -> Option<&str> {
+        self.event_type.as_deref()
+    }
+    pub fn data(&self) -> &Value {
+        &self.data
+    }
+    pub fn message_id(&self) -> Option<&str> {
+        self.message_id.as_deref()
+    }
+    /// Parses the data of the frame, e.g. into one of the event payloads of this module
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(T::deserialize(&self.data)?)
+    }
+}
+
+/// A user reacted to a message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelMessageReactionCreated {
+    #[serde(rename = "serverId")]
+    server: Option<ServerId>,
+    reaction: MessageReaction,
+}
+impl ChannelMessageReactionCreated {
+    /// Server of the message, `None` in DMs
+    pub fn server(&self) -> Option<&ServerId> {
+        self.server.as_ref()
+    }
+    pub fn reaction(&self) -> &MessageReaction {
+        &self.reaction
+    }
+}
+
+/// A user removed their reaction from a message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelMessageReactionDeleted {
+    #[serde(rename = "serverId")]
+    server: Option<ServerId>,
+    reaction: MessageReaction,
+}
+impl ChannelMessageReactionDeleted {
+    /// Server of the message, `None` in DMs
+    pub fn server(&self) -> Option<&ServerId> {
+        self.server.as_ref()
+    }
+    pub fn reaction(&self) -> &MessageReaction {
+        &self.reaction
+    }
+}
+
+/// A member's nickname changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerMemberUpdated {
+    #[serde(rename = "serverId")]
+    server: ServerId,
+    #[serde(rename = "userInfo")]
+    user_info: UpdatedUserInfo,
+}
+impl ServerMemberUpdated {
+    pub fn server(&self) -> &ServerId {
+        &self.server
+    }
+    pub fn user(&self) -> &UserId {
+        &self.user_info.id
+    }
+    /// New nickname of the member, `None` when it was removed
+    pub fn nickname(&self) -> Option<&str> {
+        self.user_info.nickname.as_deref()
+    }
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UpdatedUserInfo {
+    id: UserId,
+    #[serde(default)]
+    nickname: Option<String>,
+}
+
+/// The roles of one or more members changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerRolesUpdated {
+    #[serde(rename = "serverId")]
+    server: ServerId,
+    #[serde(rename = "memberRoleIds")]
+    members: Vec<MemberRoleIds>,
+}
+impl ServerRolesUpdated {
+    pub fn server(&self) -> &ServerId {
+        &self.server
+    }
+    /// Members whose roles changed, with all the roles they have now
+    pub fn members(&self) -> &[MemberRoleIds] {
+        &self.members
+    }
+}
+
+/// Roles a member has after a [`ServerRolesUpdated`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberRoleIds {
+    #[serde(rename = "userId")]
+    user: UserId,
+    #[serde(rename = "roleIds")]
+    roles: HashSet<RoleId>,
+}
+impl MemberRoleIds {
+    pub fn user(&self) -> &UserId {
+        &self.user
+    }
+    pub fn roles(&self) -> &HashSet<RoleId> {
+        &self.roles
+    }
+    /// Roles the member gained, given the roles they had `before` (e.g. from [`ServerMember::roles`](crate::member::ServerMember::roles))
+    pub fn added<'a>(&'a self, before: &'a HashSet<RoleId>) -> impl Iterator<Item = RoleId> + 'a {
+        self.roles.difference(before).copied()
+    }
+    /// Roles the member lost, given the roles they had `before`
+    pub fn removed<'a>(&'a self, before: &'a HashSet<RoleId>) -> impl Iterator<Item = RoleId> + 'a {
+        before.difference(&self.roles).copied()
+    }
+}
+
+server_event! {
+    /// A channel was created
+    ServerChannelCreated { channel: ServerChannel }
+}
+server_event! {
+    /// A channel's settings changed, holds the channel as it is now
+    ServerChannelUpdated { channel: ServerChannel }
+}
+server_event! {
+    /// A channel was deleted, holds the channel as it was
+    ServerChannelDeleted { channel: ServerChannel }
+}
+
+#[cfg(feature = "docs")]
+server_event! {
+    /// A doc was created
+    DocCreated { doc: Doc }
+}
+#[cfg(feature = "docs")]
+server_event! {
+    /// A doc was edited, holds the doc as it is now
+    DocUpdated { doc: Doc }
+}
+#[cfg(feature = "docs")]
+server_event! {
+    /// A doc was deleted, holds the doc as it was
+    DocDeleted { doc: Doc }
+}
+#[cfg(feature = "list")]
+server_event! {
+    /// A list item was created
+    ListItemCreated { list_item: ListItem }
+}
+#[cfg(feature = "list")]
+server_event! {
+    /// A list item or its note was edited
+    ListItemUpdated { list_item: ListItem }
+}
+#[cfg(feature = "list")]
+server_event! {
+    /// A list item was deleted, holds the item as it was
+    ListItemDeleted { list_item: ListItem }
+}
+#[cfg(feature = "list")]
+server_event! {
+    /// A list item was marked as complete
+    ListItemCompleted { list_item: ListItem }
+}
+#[cfg(feature = "list")]
+server_event! {
+    /// A list item was marked as incomplete
+    ListItemUncompleted { list_item: ListItem }
+}
+#[cfg(feature = "forums")]
+server_event! {
+    /// A forum topic was created
+    ForumTopicCreated { forum_topic: ForumThread }
+}
+#[cfg(feature = "forums")]
+server_event! {
+    /// A forum topic was edited
+    ForumTopicUpdated { forum_topic: ForumThread }
+}
+#[cfg(feature = "forums")]
+server_event! {
+    /// A forum topic was deleted, holds the topic as it was
+    ForumTopicDeleted { forum_topic: ForumThread }
+}
+#[cfg(feature = "forums")]
+server_event! {
+    /// A forum topic was pinned
+    ForumTopicPinned { forum_topic: ForumThread }
+}
+#[cfg(feature = "forums")]
+server_event! {
+    /// A forum topic was unpinned
+    ForumTopicUnpinned { forum_topic: ForumThread }
+}
+#[cfg(feature = "forums")]
+server_event! {
+    /// A forum topic was locked, so it can't be replied to
+    ForumTopicLocked { forum_topic: ForumThread }
+}
+#[cfg(feature = "forums")]
+server_event! {
+    /// A forum topic was unlocked
+    ForumTopicUnlocked { forum_topic: ForumThread }
+}
+
+/// The bot was added to a server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BotServerMembershipCreated {
+    server: Server,
+    #[serde(rename = "createdBy")]
+    created_by: UserId,
+}
+impl BotServerMembershipCreated {
+    pub fn server(&self) -> &Server {
+        &self.server
+    }
+    /// User who added the bot
+    pub fn invited_by(&self) -> &UserId {
+        &self.created_by
+    }
+}
+
+/// The bot was removed from a server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BotServerMembershipDeleted {
+    server: Server,
+    #[serde(rename = "deletedBy")]
+    deleted_by: UserId,
+}
+impl BotServerMembershipDeleted {
+    pub fn server(&self) -> &Server {
+        &self.server
+    }
+    /// User who removed the bot
+    pub fn removed_by(&self) -> &UserId {
+        &self.deleted_by
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio_stream::StreamExt;
+
+use crate::channel::ChannelId;
+use crate::error::Result;
+use crate::http::Http;
+use crate::message::GetChannelMessagesRequest;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One JSON message per line
+    Ndjson,
+    /// A single JSON array of messages
+    Json,
+}
+
+/// How far an export got. Messages are exported newest first, so `oldest` is the
+/// creation time of the last message written and resuming continues before it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportCheckpoint {
+    oldest: Option<DateTime<Utc>>,
+    exported: u64,
+}
+impl ExportCheckpoint {
+    pub fn oldest(&self) -> Option<&DateTime<Utc>> {
+        self.oldest.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn oldest_time(&self) -> Option<time::OffsetDateTime> {
+        self.oldest
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+    pub fn exported(&self) -> u64 {
+        self.exported
+    }
+}
+
+type Progress<'a> = Box<dyn FnMut(&ExportCheckpoint) + Send + 'a>;
+
+pub struct ExportMessagesRequest<'a> {
+    client: Http,
+    channel: &'a ChannelId,
+    format: ExportFormat,
+    resume: Option<ExportCheckpoint>,
+    include_private: Option<bool>,
+    progress: Option<Progress<'a>>,
+}
+impl Debug for ExportMessagesRequest<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExportMessagesRequest")
+            .field("client", &self.client)
+            .field("channel", &self.channel)
+            .field("format", &self.format)
+            .field("resume", &self.resume)
+            .field("include_private", &self.include_private)
+            .finish_non_exhaustive()
+    }
+}
+impl<'a> ExportMessagesRequest<'a> {
+    pub fn new(client: Http, channel: &'a ChannelId) -> Self {
+        Self {
+            client,
+            channel,
+            format: ExportFormat::Ndjson,
+            resume: None,
+            include_private: None,
+            progress: None,
+        }
+    }
+    /// Writes the channel history to `writer` and returns the final checkpoint.
+    ///
+    /// If the export fails part way, the last checkpoint passed to the progress callback
+    /// can be given to [`ExportMessagesRequest::resume`] to continue where it stopped.
+    /// With [`ExportFormat::Ndjson`] the output of the resumed export can simply be appended.
+    pub async fn send<W: AsyncWrite + Unpin + ?Sized>(
+        mut self,
+        writer: &mut W,
+    ) -> Result<ExportCheckpoint> {
+        let mut checkpoint = self.resume.unwrap_or(ExportCheckpoint {
+            oldest: None,
+            exported: 0,
+        });
+        let mut request = GetChannelMessagesRequest::new(self.client, self.channel);
+        if let Some(oldest) = checkpoint.oldest {
+            request = request.before(oldest);
+        }
+        if let Some(include_private) = self.include_private {
+            request = request.include_private(include_private);
+        }
+        let messages = request.send();
+        tokio::pin!(messages);
+
+        if self.format == ExportFormat::Json {
+            writer.write_all(b"[").await?;
+        }
+        let mut first = true;
+        while let Some(message) = messages.next().await {
+            let message = message?;
+            let mut line = serde_json::to_vec(&message)?;
+            match self.format {
+                ExportFormat::Ndjson => line.push(b'\n'),
+                ExportFormat::Json if !first => line.insert(0, b','),
+                ExportFormat::Json => {}
+            }
+            writer.write_all(&line).await?;
+            first = false;
+
+            checkpoint.oldest = Some(*message.created_at());
+            checkpoint.exported += 1;
+            if let Some(progress) = &mut self.progress {
+                progress(&checkpoint);
+            }
+        }
+        if self.format == ExportFormat::Json {
+            writer.write_all(b"]").await?;
+        }
+        writer.flush().await?;
+
+        Ok(checkpoint)
+    }
+    pub fn format(mut self, format: ExportFormat) -> Self {
+        self.format = format;
+        self
+    }
+    /// Continues a previous export from its checkpoint
+    pub fn resume(mut self, checkpoint: ExportCheckpoint) -> Self {
+        self.resume = Some(checkpoint);
+        self
+    }
+    pub fn include_private(mut self, include_private: bool) -> Self {
+        self.include_private = Some(include_private);
+        self
+    }
+    /// Called after every exported message
+    pub fn progress(mut self, progress: impl FnMut(&ExportCheckpoint) + Send + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
@@ -0,0 +1,388 @@
+//! Sample API payloads and the models parsed from them, for testing bots without a server
+//!
+//! Each model the REST API returns has the raw JSON as a constant (e.g. [`CHAT_MESSAGE`]) and a
+//! function returning it parsed (e.g. [`chat_message()`]), as do a few gateway events and the
+//! [`ExportCheckpoint`] exports save. Calendar events aren't modelled yet, so they have none.
+
+use serde::de::DeserializeOwned;
+
+use crate::bans::ServerMemberBan;
+use crate::channel::ServerChannel;
+#[cfg(feature = "docs")]
+use crate::docs::Doc;
+use crate::events::{
+    BotServerMembershipCreated, ChannelMessageReactionCreated, ServerChannelCreated,
+    ServerRolesUpdated,
+};
+use crate::export::ExportCheckpoint;
+#[cfg(feature = "forums")]
+use crate::forums::ForumThread;
+#[cfg(feature = "list")]
+use crate::list::{ListItem, ListItemSummary, ListNote};
+use crate::media::{UploadedMedia, UrlSignature};
+use crate::member::{ServerMember, ServerMemberSummary, User, UserSummary};
+use crate::message::ChatMessage;
+use crate::reactions::Reaction;
+use crate::server::Server;
+#[cfg(feature = "social")]
+use crate::social::SocialLink;
+use crate::GuildedClient;
+
+/// Token in the right format, for clients that are never meant to reach the API
+pub const TOKEN: &str = "gapi_dGVzdA==";
+pub const SERVER_ID: &str = "wlVr3Ggl";
+pub const USER_ID: &str = "Ann6LewA";
+pub const CHANNEL_ID: &str = "00000000-0000-0000-0000-000000000000";
+pub const MESSAGE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+pub const CHAT_MESSAGE: &str = r#"{
+    "id": "00000000-0000-0000-0000-000000000001",
+    "type": "default",
+    "serverId": "wlVr3Ggl",
+    "channelId": "00000000-0000-0000-0000-000000000000",
+    "content": "Hello **world**!",
+    "embeds": [
+        {
+            "title": "embed title",
+            "description": "embed description",
+            "url": "https://www.guilded.gg",
+            "color": 6118369,
+            "footer": { "text": "footer text" },
+            "timestamp": "2022-04-12T22:14:36.737Z",
+            "fields": [{ "name": "hello", "value": "there", "inline": true }]
+        }
+    ],
+    "replyMessageIds": ["00000000-0000-0000-0000-000000000002"],
+    "isPrivate": false,
+    "createdAt": "2022-04-12T22:14:36.737Z",
+    "createdBy": "Ann6LewA",
+    "createdByWebhookId": null,
+    "updatedAt": "2022-04-12T22:15:00.000Z"
+}"#;
+
+pub const USER: &str = r#"{
+    "id": "Ann6LewA",
+    "type": "user",
+    "name": "Leopold Stotch",
+    "avatar": "https://cdn.gilcdn.com/UserAvatar/6dc417befe51bbca91b902984f113f89-Large.webp",
+    "banner": "https://cdn.gilcdn.com/UserBanner/f6d0b8d4a6a6e6c1b0b0a9d9c3a1a5e2-Hero.png",
+    "createdAt": "2021-06-15T20:15:00.706Z",
+    "status": {
+        "content": "Doing some coding",
+        "emoteId": 90002569
+    }
+}"#;
+
+pub const SERVER_MEMBER: &str = r#"{
+    "user": {
+        "id": "Ann6LewA",
+        "type": "user",
+        "name": "Leopold Stotch",
+        "avatar": "https://img.guildedcdn.com/asset/DefaultUserAvatars/profile_1.png",
+        "banner": null,
+        "createdAt": "2021-06-15T20:15:00.706Z"
+    },
+    "roleIds": [31, 28],
+    "nickname": "Professor Chaos",
+    "joinedAt": "2021-07-15T20:15:00.706Z",
+    "isOwner": false
+}"#;
+
+pub const USER_SUMMARY: &str = r#"{
+    "id": "Ann6LewA",
+    "type": "user",
+    "name": "Leopold Stotch",
+    "avatar": null
+}"#;
+
+pub const SERVER_MEMBER_SUMMARY: &str = r#"{
+    "user": {
+        "id": "Ann6LewA",
+        "type": "user",
+        "name": "Leopold Stotch",
+        "avatar": null
+    },
+    "roleIds": [31]
+}"#;
+
+pub const SERVER_CHANNEL: &str = r#"{
+    "id": "00000000-0000-0000-0000-000000000000",
+    "type": "chat",
+    "name": "The Dank Cellar",
+    "topic": "Dank memes ONLY",
+    "createdAt": "2021-06-15T20:15:00.706Z",
+    "createdBy": "Ann6LewA",
+    "updatedAt": "2021-06-15T20:16:00.706Z",
+    "serverId": "wlVr3Ggl",
+    "parentId": null,
+    "categoryId": 1,
+    "groupId": "ZyQm9L0d",
+    "isPublic": false,
+    "visibility": "public",
+    "archivedBy": null,
+    "archivedAt": null
+}"#;
+
+pub const SERVER_MEMBER_BAN: &str = r#"{
+    "user": {
+        "id": "Ann6LewA",
+        "type": "user",
+        "name": "Leopold Stotch",
+        "avatar": null
+    },
+    "reason": "More toxic than a poison Pokémon",
+    "createdBy": "EdVMVKR4",
+    "createdAt": "2021-06-15T20:15:00.706Z"
+}"#;
+
+pub const REACTION: &str = r#"{
+    "id": 90000000,
+    "serverId": "wlVr3Ggl",
+    "createdAt": "2022-04-12T22:14:36.737Z",
+    "createdBy": "Ann6LewA",
+    "createdByWebhookId": null
+}"#;
+
+pub const CHANNEL_MESSAGE_REACTION_CREATED: &str = r#"{
+    "serverId": "wlVr3Ggl",
+    "reaction": {
+        "channelId": "00000000-0000-0000-0000-000000000000",
+        "messageId": "00000000-0000-0000-0000-000000000001",
+        "createdBy": "Ann6LewA",
+        "emote": {
+            "id": 90002569,
+            "name": "grinning",
+            "url": "https://img.guildedcdn.com/asset/Emojis/grinning.webp"
+        }
+    }
+}"#;
+
+pub const SERVER_ROLES_UPDATED: &str = r#"{
+    "serverId": "wlVr3Ggl",
+    "memberRoleIds": [{ "userId": "Ann6LewA", "roleIds": [31, 35] }]
+}"#;
+
+pub const SERVER_CHANNEL_CREATED: &str = r#"{
+    "serverId": "wlVr3Ggl",
+    "channel": {
+        "id": "00000000-0000-0000-0000-000000000000",
+        "type": "chat",
+        "name": "The Dank Cellar",
+        "topic": null,
+        "createdAt": "2021-06-15T20:15:00.706Z",
+        "createdBy": "Ann6LewA",
+        "updatedAt": null,
+        "serverId": "wlVr3Ggl",
+        "parentId": null,
+        "categoryId": null,
+        "groupId": "ZyQm9L0d",
+        "archivedBy": null,
+        "archivedAt": null
+    }
+}"#;
+
+pub const SERVER: &str = r#"{
+    "id": "wlVr3Ggl",
+    "ownerId": "EdVMVKR4",
+    "type": "community",
+    "name": "Guilded",
+    "url": "Guilded-Official",
+    "about": "The Official Guilded Server! For devs, friends, and fans alike!",
+    "avatar": "https://img.guildedcdn.com/ContentMediaGenericFiles/1a8ad8c4b4a8f8c3b1a4d8e5f9e7c6b2-Full.webp",
+    "timezone": "America/Los Angeles (PST/PDT)",
+    "isVerified": true,
+    "defaultChannelId": "00000000-0000-0000-0000-000000000000",
+    "createdAt": "2018-10-05T22:24:05.962Z"
+}"#;
+
+pub const BOT_SERVER_MEMBERSHIP_CREATED: &str = r#"{
+    "server": {
+        "id": "wlVr3Ggl",
+        "ownerId": "EdVMVKR4",
+        "type": "community",
+        "name": "Guilded",
+        "url": "Guilded-Official",
+        "about": "The Official Guilded Server! For devs, friends, and fans alike!",
+        "avatar": "https://img.guildedcdn.com/ContentMediaGenericFiles/1a8ad8c4b4a8f8c3b1a4d8e5f9e7c6b2-Full.webp",
+        "timezone": "America/Los Angeles (PST/PDT)",
+        "isVerified": true,
+        "defaultChannelId": "00000000-0000-0000-0000-000000000000",
+        "createdAt": "2018-10-05T22:24:05.962Z"
+    },
+    "createdBy": "Ann6LewA"
+}"#;
+
+#[cfg(feature = "docs")]
+pub const DOC: &str = r#"{
+    "id": 1,
+    "serverId": "wlVr3Ggl",
+    "channelId": "00000000-0000-0000-0000-000000000000",
+    "title": "HOW-TO: Smoke These Meats with Sweet Baby Ray's",
+    "content": "Spicy jalapeno bacon ipsum dolor amet",
+    "createdAt": "2021-06-15T20:15:00.706Z",
+    "createdBy": "Ann6LewA",
+    "updatedAt": "2021-06-15T20:16:00.706Z",
+    "updatedBy": "Ann6LewA"
+}"#;
+
+#[cfg(feature = "forums")]
+pub const FORUM_THREAD: &str = r#"{
+    "id": 123456,
+    "serverId": "wlVr3Ggl",
+    "channelId": "00000000-0000-0000-0000-000000000000",
+    "title": "Welcome new members!!",
+    "content": "Please introduce yourself in this topic!!!",
+    "createdAt": "2021-06-15T20:15:00.706Z",
+    "createdBy": "Ann6LewA",
+    "updatedAt": "2021-06-15T20:16:00.706Z"
+}"#;
+
+#[cfg(feature = "list")]
+pub const LIST_ITEM: &str = r#"{
+    "id": "00000000-0000-0000-0000-000000000003",
+    "serverId": "wlVr3Ggl",
+    "channelId": "00000000-0000-0000-0000-000000000000",
+    "message": "Remember to say hello **world**!",
+    "createdAt": "2021-06-15T20:15:00.706Z",
+    "createdBy": "Ann6LewA",
+    "updatedAt": "2021-06-15T20:16:00.706Z",
+    "updatedBy": "Ann6LewA",
+    "parentListItemId": "00000000-0000-0000-0000-000000000004",
+    "completedAt": "2021-06-15T20:17:00.706Z",
+    "completedBy": "Ann6LewA",
+    "note": {
+        "createdAt": "2021-06-15T20:15:00.706Z",
+        "createdBy": "Ann6LewA",
+        "content": "Duly noted"
+    }
+}"#;
+
+#[cfg(feature = "list")]
+pub const LIST_ITEM_SUMMARY: &str = r#"{
+    "id": "00000000-0000-0000-0000-000000000003",
+    "serverId": "wlVr3Ggl",
+    "channelId": "00000000-0000-0000-0000-000000000000",
+    "createdAt": "2021-06-15T20:15:00.706Z",
+    "createdBy": "Ann6LewA",
+    "updatedAt": "2021-06-15T20:16:00.706Z",
+    "updatedBy": "Ann6LewA",
+    "note": {
+        "createdAt": "2021-06-15T20:15:00.706Z",
+        "createdBy": "Ann6LewA"
+    }
+}"#;
+
+#[cfg(feature = "list")]
+pub const LIST_NOTE: &str = r#"{
+    "createdAt": "2021-06-15T20:15:00.706Z",
+    "createdBy": "Ann6LewA",
+    "updatedAt": "2021-06-15T20:16:00.706Z",
+    "updatedBy": "Ann6LewA",
+    "content": "Duly noted"
+}"#;
+
+pub const UPLOADED_MEDIA: &str = r#"{
+    "url": "https://img.guildedcdn.com/ContentMediaGenericFiles/4c5b9e2f7d1a3e8b6f0c2d4a9e1b7c3f-Full.webp"
+}"#;
+
+pub const URL_SIGNATURE: &str = r#"{
+    "url": "https://img.guildedcdn.com/ContentMediaGenericFiles/4c5b9e2f7d1a3e8b6f0c2d4a9e1b7c3f-Full.webp",
+    "signature": "https://cdn.gilcdn.com/ContentMediaGenericFiles/4c5b9e2f7d1a3e8b6f0c2d4a9e1b7c3f-Full.webp?w=1920&h=1080&sig=3q2-7w"
+}"#;
+
+pub const EXPORT_CHECKPOINT: &str = r#"{
+    "oldest": "2021-06-15T20:15:00.706Z",
+    "exported": 250
+}"#;
+
+#[cfg(feature = "social")]
+pub const SOCIAL_LINK: &str = r#"{
+    "type": "twitch",
+    "userId": "Ann6LewA",
+    "handle": "leopoldstotch",
+    "serviceId": "123456789",
+    "createdAt": "2021-06-15T20:15:00.706Z"
+}"#;
+
+/// Client built with [`TOKEN`], e.g. to build requests and dry run them
+pub fn client() -> GuildedClient {
+    GuildedClient::new(TOKEN).expect("the fixture token is well-formed")
+}
+
+/// Parses a sample payload, which is known to be valid
+fn parse<T: DeserializeOwned>(json: &str) -> T {
+    serde_json::from_str(json).expect("fixtures match the models")
+}
+
+pub fn chat_message() -> ChatMessage {
+    parse(CHAT_MESSAGE)
+}
+pub fn user() -> User {
+    parse(USER)
+}
+pub fn user_summary() -> UserSummary {
+    parse(USER_SUMMARY)
+}
+pub fn server_member() -> ServerMember {
+    parse(SERVER_MEMBER)
+}
+pub fn server_member_summary() -> ServerMemberSummary {
+    parse(SERVER_MEMBER_SUMMARY)
+}
+pub fn server_channel() -> ServerChannel {
+    parse(SERVER_CHANNEL)
+}
+pub fn server_member_ban() -> ServerMemberBan {
+    parse(SERVER_MEMBER_BAN)
+}
+pub fn reaction() -> Reaction {
+    parse(REACTION)
+}
+pub fn channel_message_reaction_created() -> ChannelMessageReactionCreated {
+    parse(CHANNEL_MESSAGE_REACTION_CREATED)
+}
+pub fn server_roles_updated() -> ServerRolesUpdated {
+    parse(SERVER_ROLES_UPDATED)
+}
+pub fn server_channel_created() -> ServerChannelCreated {
+    parse(SERVER_CHANNEL_CREATED)
+}
+pub fn server() -> Server {
+    parse(SERVER)
+}
+pub fn bot_server_membership_created() -> BotServerMembershipCreated {
+    parse(BOT_SERVER_MEMBERSHIP_CREATED)
+}
+#[cfg(feature = "docs")]
+pub fn doc() -> Doc {
+    parse(DOC)
+}
+#[cfg(feature = "forums")]
+pub fn forum_thread() -> ForumThread {
+    parse(FORUM_THREAD)
+}
+#[cfg(feature = "list")]
+pub fn list_item() -> ListItem {
+    parse(LIST_ITEM)
+}
+#[cfg(feature = "list")]
+pub fn list_item_summary() -> ListItemSummary {
+    parse(LIST_ITEM_SUMMARY)
+}
+#[cfg(feature = "list")]
+pub fn list_note() -> ListNote {
+    parse(LIST_NOTE)
+}
+pub fn uploaded_media() -> UploadedMedia {
+    parse(UPLOADED_MEDIA)
+}
+pub fn url_signature() -> UrlSignature {
+    parse(URL_SIGNATURE)
+}
+pub fn export_checkpoint() -> ExportCheckpoint {
+    parse(EXPORT_CHECKPOINT)
+}
+#[cfg(feature = "social")]
+pub fn social_link() -> SocialLink {
+    parse(SOCIAL_LINK)
+}
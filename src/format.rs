@@ -0,0 +1,148 @@
+//! Helpers for formatting message content
+
+use std::fmt::Write;
+
+use crate::message::MAX_CONTENT_LENGTH;
+
+/// Wraps `code` in a fenced code block, highlighted as `language` if given.
+///
+/// Backtick fences inside `code` are broken up so they can't end the block early.
+pub fn code_block(code: &str, language: Option<&str>) -> String {
+    let code = code.replace("```", "`\u{200b}``");
+    format!(
+        "```{}\n{}\n```",
+        language.unwrap_or_default(),
+        code.trim_end()
+    )
+}
+
+/// Horizontal alignment of a table column
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// Monospace table, rendered inside a code block so the columns line up
+#[derive(Debug, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    align: Vec<Align>,
+    rows: Vec<Vec<String>>,
+    max_width: Option<usize>,
+}
+impl Table {
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+    /// Sets the alignment of the column at `column` (default: left)
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if self.align.len() <= column {
+            self.align.resize(column + 1, Align::Left);
+        }
+        self.align[column] = align;
+        self
+    }
+    /// Truncates cells to at most `width` characters, ending them with `…`
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width.max(1));
+        self
+    }
+    pub fn row<I, S>(mut self, row: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.rows
+            .push(row.into_iter().map(|cell| cell.to_string()).collect());
+        self
+    }
+    /// Renders the table as a code block. Rows that would push it past the message length limit are
+    /// left out, ending the table with a line counting them.
+    pub fn render(&self) -> String {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.headers.len()])
+            .max()
+            .unwrap_or_default();
+        let cell = |row: &[String], column: usize| -> String {
+            let cell = row.get(column).map(String::as_str).unwrap_or_default();
+            truncate(cell, self.max_width)
+        };
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| (0..columns).map(|column| cell(row, column)).collect())
+            .collect();
+        let headers: Vec<String> = (0..columns)
+            .map(|column| cell(&self.headers, column))
+            .collect();
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                rows.iter()
+                    .chain([&headers])
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let mut lines = Vec::new();
+        if !self.headers.is_empty() {
+            lines.push(self.line(&headers, &widths));
+            let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+            lines.push(rule.join("-+-"));
+        }
+        // Room for the fences and the "... more rows" line
+        let budget = MAX_CONTENT_LENGTH - 40;
+        let mut length: usize = lines.iter().map(|line| line.chars().count() + 1).sum();
+        for (i, row) in rows.iter().enumerate() {
+            let line = self.line(row, &widths);
+            length += line.chars().count() + 1;
+            if length > budget {
+                lines.push(format!("... {} more rows", rows.len() - i));
+                break;
+            }
+            lines.push(line);
+        }
+        code_block(&lines.join("\n"), None)
+    }
+    fn line(&self, cells: &[String], widths: &[usize]) -> String {
+        let mut line = String::new();
+        for (column, (cell, &width)) in cells.iter().zip(widths).enumerate() {
+            if column > 0 {
+                line.push_str(" | ");
+            }
+            let _ = match self.align.get(column).copied().unwrap_or_default() {
+                Align::Left => write!(line, "{cell:<width$}"),
+                Align::Right => write!(line, "{cell:>width$}"),
+                Align::Center => write!(line, "{cell:^width$}"),
+            };
+        }
+        line.trim_end().to_owned()
+    }
+}
+
+fn truncate(cell: &str, width: Option<usize>) -> String {
+    // Newlines would break the row apart
+    let cell = cell.replace('\n', " ");
+    match width {
+        Some(width) if cell.chars().count() > width => {
+            let mut truncated: String = cell.chars().take(width - 1).collect();
+            truncated.push('…');
+            truncated
+        }
+        _ => cell,
+    }
+}
@@ -4,14 +4,18 @@ use std::result::Result as StdResult;
 use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
 
 use crate::channel::ChannelId;
-use crate::error::Result;
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
+use crate::id;
 use crate::member::{ServerId, UserId};
 use crate::message::WebhookId;
-use crate::API_BASE;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 #[repr(transparent)]
@@ -29,7 +33,7 @@ impl<'de> Deserialize<'de> for ForumId {
     where
         D: serde::Deserializer<'de>,
     {
-        u32::deserialize(deserializer).map(Self)
+        id::deserialize_u32(deserializer).map(Self)
     }
 }
 impl ForumId {
@@ -94,6 +98,24 @@ pub struct ForumThread {
     #[serde(rename = "updatedAt")]
     updated: Option<DateTime<Utc>>,
 }
+impl ForumThread {
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+    pub fn updated(&self) -> Option<&DateTime<Utc>> {
+        self.updated.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+}
 
 #[derive(Debug, Serialize)]
 struct CreateThreadBody<'a> {
@@ -113,13 +135,13 @@ struct CreateThreadResponse {
 }
 #[derive(Debug)]
 pub struct CreateThreadRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     title: &'a str,
     content: &'a str,
 }
 impl<'a> CreateThreadRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, title: &'a str, content: &'a str) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, title: &'a str, content: &'a str) -> Self {
         Self {
             client,
             channel,
@@ -128,16 +150,30 @@ impl<'a> CreateThreadRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<ForumThread> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let body = CreateThreadBody::new(self.title, self.content);
         let request = self
             .client
-            .post(format!("{API_BASE}/channels/{}/forum", self.channel))
+            .post(Route::Forum {
+                channel: self.channel,
+            })
             .json(&body)
             .build()?;
 
-        let response = self.client.execute(request).await?.error_for_status()?;
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ForumThread, ResponseMeta)> {
+        let request = self.build_request()?;
+
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let thread: CreateThreadResponse = response.json().await?;
 
-        Ok(thread.thread)
+        Ok((thread.thread, meta))
     }
 }
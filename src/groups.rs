@@ -3,12 +3,9 @@ use std::ops::Deref;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
 use crate::member::UserId;
-use crate::API_BASE;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 #[repr(transparent)]
@@ -61,58 +58,18 @@ impl FromStr for GroupId {
     }
 }
 
-#[derive(Debug)]
-pub struct AddGroupMemberRequest<'a> {
-    client: Client,
-    group: &'a GroupId,
-    user: &'a UserId,
-}
-impl<'a> AddGroupMemberRequest<'a> {
-    pub fn new(client: Client, group: &'a GroupId, user: &'a UserId) -> Self {
-        Self {
-            client,
-            group,
-            user,
-        }
-    }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .put(format!(
-                "{API_BASE}/groups/{}/members/{}",
-                self.group, self.user
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
-
-        Ok(())
+request! {
+    pub struct AddGroupMemberRequest<'a> {
+        group: &'a GroupId,
+        user: &'a UserId,
     }
+    PUT GroupMember => ()
 }
 
-#[derive(Debug)]
-pub struct DeleteGroupMemberRequest<'a> {
-    client: Client,
-    group: &'a GroupId,
-    user: &'a UserId,
-}
-impl<'a> DeleteGroupMemberRequest<'a> {
-    pub fn new(client: Client, group: &'a GroupId, user: &'a UserId) -> Self {
-        Self {
-            client,
-            group,
-            user,
-        }
-    }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .delete(format!(
-                "{API_BASE}/groups/{}/members/{}",
-                self.group, self.user
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
-
-        Ok(())
+request! {
+    pub struct DeleteGroupMemberRequest<'a> {
+        group: &'a GroupId,
+        user: &'a UserId,
     }
+    DELETE GroupMember => ()
 }
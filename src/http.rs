@@ -0,0 +1,269 @@
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use reqwest::{Client, Method, Request, RequestBuilder, Response, StatusCode, Url};
+
+use crate::audit::{AuditEntry, AuditSink};
+use crate::channel::ChannelId;
+use crate::clock::{Clock, TokioClock};
+use crate::latency::{Latencies, LatencyReport};
+use crate::meta::ResponseMeta;
+use crate::metrics::MetricsSink;
+use crate::queue::{ChannelQueues, Throttle, Turn};
+use crate::routes::{template, ApiVersion, Route};
+
+/// Request a response answers, stored in its extensions
+#[derive(Debug, Clone)]
+pub(crate) struct Origin {
+    pub(crate) method: Method,
+    /// Kept here too as responses rebuilt from parts lose their URL
+    pub(crate) url: Url,
+}
+
+/// How rate limited requests are retried, see [`GuildedClientBuilder::rate_limit_retries`](crate::GuildedClientBuilder::rate_limit_retries)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    pub(crate) retries: u32,
+    /// Wait used when a rate limited response doesn't say how long to wait
+    pub(crate) default_wait: Duration,
+}
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 5,
+            default_wait: Duration::from_secs(1),
+        }
+    }
+}
+
+/// HTTP client the requests are sent through, with the hooks configured on the [`GuildedClient`](crate::GuildedClient).
+///
+/// Cloning only bumps a reference count, as everything is shared behind a single [`Arc`].
+#[derive(Clone)]
+pub struct Http {
+    inner: Arc<Inner>,
+}
+struct Inner {
+    client: Client,
+    /// Client without the bot token, for requests that leave the API (CDN downloads, media uploads)
+    assets: Client,
+    /// Prefix of every route's URL, see [`GuildedClientBuilder::base_url`](crate::GuildedClientBuilder::base_url)
+    base_url: String,
+    audit: Option<Arc<dyn AuditSink>>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    latencies: Latencies,
+    /// Set when messages to the same channel should be sent in order
+    queues: Option<ChannelQueues>,
+    throttle: Option<Throttle>,
+    retry: RetryPolicy,
+    /// Until when every request holds off after one was rate limited, as the limit is shared by
+    /// the whole bot rather than kept per route
+    rate_limited_until: Mutex<Option<tokio::time::Instant>>,
+    clock: Arc<dyn Clock>,
+}
+impl Http {
+    pub(crate) fn new(client: Client, assets: Client) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                client,
+                assets,
+                base_url: ApiVersion::V1.base_url(),
+                audit: None,
+                metrics: None,
+                latencies: Latencies::default(),
+                queues: None,
+                throttle: None,
+                retry: RetryPolicy::default(),
+                rate_limited_until: Mutex::new(None),
+                clock: Arc::new(TokioClock),
+            }),
+        }
+    }
+    pub(crate) fn assets(&self) -> &Client {
+        &self.inner.assets
+    }
+    /// Settings can only change while the client is being built, before it's shared
+    fn inner_mut(&mut self) -> &mut Inner {
+        Arc::get_mut(&mut self.inner).expect("Http is configured before it's cloned")
+    }
+    pub(crate) fn base_url(mut self, base_url: &str) -> Self {
+        self.inner_mut().base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+    pub(crate) fn audit(mut self, audit: Option<Arc<dyn AuditSink>>) -> Self {
+        self.inner_mut().audit = audit;
+        self
+    }
+    pub(crate) fn metrics(mut self, metrics: Option<Arc<dyn MetricsSink>>) -> Self {
+        self.inner_mut().metrics = metrics;
+        self
+    }
+    pub(crate) fn ordered_sends(mut self, enable: bool) -> Self {
+        self.inner_mut().queues = enable.then(ChannelQueues::default);
+        self
+    }
+    pub(crate) fn throttle_sends(mut self, throttle: Option<Throttle>) -> Self {
+        self.inner_mut().throttle = throttle;
+        self
+    }
+    pub(crate) fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.inner_mut().retry = retry;
+        self
+    }
+    pub(crate) fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.inner_mut().clock = clock;
+        self
+    }
+    pub(crate) fn retry(&self) -> RetryPolicy {
+        self.inner.retry
+    }
+    /// Waits `wait` on the client's clock
+    pub(crate) async fn sleep(&self, wait: Duration) {
+        self.inner.clock.sleep(wait).await
+    }
+    pub(crate) fn throttle(&self) -> Option<&Throttle> {
+        self.inner.throttle.as_ref()
+    }
+    /// Waits until it's this message's turn to be sent to `channel`, if sends are ordered
+    pub(crate) async fn send_turn(&self, channel: &ChannelId) -> Option<Turn> {
+        match &self.inner.queues {
+            Some(queues) => Some(queues.turn(channel).await),
+            None => None,
+        }
+    }
+    /// URL of `path`, e.g. `/channels`, relative to the API base
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.inner.base_url)
+    }
+    /// Starts a request to an API route
+    pub fn request(&self, method: Method, route: Route<'_>) -> RequestBuilder {
+        self.inner.client.request(method, self.url(&route.path()))
+    }
+    pub fn get(&self, route: Route<'_>) -> RequestBuilder {
+        self.request(Method::GET, route)
+    }
+    pub fn post(&self, route: Route<'_>) -> RequestBuilder {
+        self.request(Method::POST, route)
+    }
+    pub fn put(&self, route: Route<'_>) -> RequestBuilder {
+        self.request(Method::PUT, route)
+    }
+    pub fn patch(&self, route: Route<'_>) -> RequestBuilder {
+        self.request(Method::PATCH, route)
+    }
+    pub fn delete(&self, route: Route<'_>) -> RequestBuilder {
+        self.request(Method::DELETE, route)
+    }
+    /// Holds off every request for `wait`, reporting it as a rate limit wait
+    fn rate_limited(&self, wait: Duration) {
+        if let Some(metrics) = &self.inner.metrics {
+            metrics.rate_limit_wait(wait);
+        }
+        let until = self.inner.clock.now() + wait;
+        let mut rate_limited_until = self.inner.rate_limited_until.lock().unwrap();
+        *rate_limited_until = Some(rate_limited_until.map_or(until, |current| current.max(until)));
+    }
+    /// Waits out the rate limit another request ran into, if there's one
+    async fn rate_limit(&self) {
+        loop {
+            let until = *self.inner.rate_limited_until.lock().unwrap();
+            let now = self.inner.clock.now();
+            match until {
+                // Checked again after waiting, as another request may have been limited meanwhile
+                Some(until) if until > now => self.sleep(until - now).await,
+                _ => return,
+            }
+        }
+    }
+    /// Latencies of the requests sent so far, per route
+    pub fn latency_report(&self) -> LatencyReport {
+        self.inner.latencies.report()
+    }
+    /// Sends `request`, recording its latency, its metrics and, if it changes anything, an audit log entry.
+    ///
+    /// While it's rate limited, the request is sent again after the wait the server asks for, up to
+    /// the retries of the [`RetryPolicy`]; the last response is returned as is. Requests with a
+    /// streamed body can't be resent, so they're only sent once. Until the wait is over, the
+    /// other requests sent through this client wait too.
+    pub async fn execute(&self, mut request: Request) -> reqwest::Result<Response> {
+        let retry = self.retry();
+        let mut attempt = 0;
+        loop {
+            self.rate_limit().await;
+            let resend = (attempt < retry.retries)
+                .then(|| request.try_clone())
+                .flatten();
+            let response = self.execute_once(request).await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+            let wait = ResponseMeta::new(&response)
+                .retry_after()
+                .unwrap_or(retry.default_wait);
+            self.rate_limited(wait);
+            let Some(resend) = resend else {
+                return Ok(response);
+            };
+            attempt += 1;
+            request = resend;
+        }
+    }
+    async fn execute_once(&self, request: Request) -> reqwest::Result<Response> {
+        let audit = match &self.inner.audit {
+            Some(sink) if !matches!(*request.method(), Method::GET | Method::HEAD) => {
+                Some((sink, AuditEntry::new(Utc::now(), &request)))
+            }
+            _ => None,
+        };
+        let (method, url) = (request.method().clone(), request.url().clone());
+        #[cfg(feature = "debug-body")]
+        crate::debug_body::log_request(&request);
+        #[cfg(feature = "otel")]
+        let span = crate::otel::RequestSpan::start(&request);
+        let start = Instant::now();
+        let mut response = self.inner.client.execute(request).await;
+        #[cfg(feature = "otel")]
+        span.end(&response);
+        let elapsed = start.elapsed();
+        self.inner.latencies.record(&method, &url, elapsed);
+        if let Some(metrics) = &self.inner.metrics {
+            let status = response.as_ref().ok().map(Response::status);
+            metrics.request(&method, &template(&url), status, elapsed);
+        }
+        #[cfg(feature = "debug-body")]
+        if let Ok(sent) = response {
+            response = crate::debug_body::log_response(sent).await;
+        }
+        if let Ok(response) = &mut response {
+            // Read back by `check_status` to say which request failed
+            response.extensions_mut().insert(Origin { method, url });
+        }
+        if let Some((sink, mut entry)) = audit {
+            entry.set_status(response.as_ref().ok().map(Response::status));
+            sink.record(&entry);
+        }
+        response
+    }
+}
+impl Deref for Http {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner.client
+    }
+}
+impl std::fmt::Debug for Http {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Http")
+            .field("client", &self.inner.client)
+            .field("base_url", &self.inner.base_url)
+            .field("audit", &self.inner.audit.is_some())
+            .field("metrics", &self.inner.metrics.is_some())
+            .field("ordered_sends", &self.inner.queues.is_some())
+            .field("throttle", &self.inner.throttle)
+            .field("retry", &self.inner.retry)
+            .finish()
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use reqwest::Request;
+use tokio::sync::watch;
+
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
+use crate::meta::ResponseMeta;
+
+type Shared = Arc<(Vec<u8>, ResponseMeta)>;
+
+#[derive(Debug, Clone)]
+enum Flight {
+    Pending,
+    Done(Shared),
+    Failed,
+}
+
+/// Coalesces identical GET requests that are in flight at the same time into a single call.
+///
+/// The first caller sends the request and the others wait for its response. If it fails, the
+/// waiting callers send their own request instead, so each of them gets its own error.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Inflight {
+    calls: Arc<Mutex<HashMap<String, watch::Receiver<Flight>>>>,
+}
+impl Inflight {
+    pub(crate) async fn execute(
+        &self,
+        client: &Http,
+        request: Request,
+    ) -> Result<(Vec<u8>, ResponseMeta)> {
+        let key = request.url().to_string();
+        let existing = {
+            let mut calls = self.calls.lock().unwrap();
+            match calls.get(&key) {
+                Some(receiver) => Err(receiver.clone()),
+                None => {
+                    let (sender, receiver) = watch::channel(Flight::Pending);
+                    calls.insert(key.clone(), receiver);
+                    Ok(sender)
+                }
+            }
+        };
+        match existing {
+            Ok(sender) => {
+                let _guard = Remove {
+                    inflight: self,
+                    key,
+                };
+                let result = send(client, request).await;
+                let flight = match &result {
+                    Ok(shared) => Flight::Done(shared.clone()),
+                    Err(_) => Flight::Failed,
+                };
+                let _ = sender.send(flight);
+                result.map(|shared| shared.as_ref().clone())
+            }
+            Err(mut receiver) => {
+                let flight = receiver
+                    .wait_for(|flight| !matches!(flight, Flight::Pending))
+                    .await
+                    .map(|flight| flight.clone());
+                match flight {
+                    Ok(Flight::Done(shared)) => Ok(shared.as_ref().clone()),
+                    // The first caller failed or was dropped before finishing
+                    _ => send(client, request)
+                        .await
+                        .map(|shared| shared.as_ref().clone()),
+                }
+            }
+        }
+    }
+}
+
+/// Takes a finished (or cancelled) call out of the map
+struct Remove<'a> {
+    inflight: &'a Inflight,
+    key: String,
+}
+impl Drop for Remove<'_> {
+    fn drop(&mut self) {
+        self.inflight.calls.lock().unwrap().remove(&self.key);
+    }
+}
+
+async fn send(client: &Http, request: Request) -> Result<Shared> {
+    let response = client.execute(request).await?.check_status()?;
+    let meta = ResponseMeta::new(&response);
+    let body = response.bytes().await?;
+
+    Ok(Arc::new((body.to_vec(), meta)))
+}
@@ -0,0 +1,52 @@
+use std::mem;
+
+/// Splits the items out of a JSON response shaped like `{"key": [{...}, {...}]}` as its bytes
+/// arrive, so a large list never has to be held in memory all at once
+#[derive(Debug, Default)]
+pub(crate) struct JsonItems {
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    current: Vec<u8>,
+}
+impl JsonItems {
+    /// Nesting of the items: inside the outer object, then inside the array
+    const ITEM_DEPTH: usize = 2;
+
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+    /// Feeds the next chunk of the body, returning the raw JSON of every item it completed
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        let mut items = Vec::new();
+        for &byte in chunk {
+            let inside = self.depth > Self::ITEM_DEPTH;
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+                if inside {
+                    self.current.push(byte);
+                }
+                continue;
+            }
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+            if inside || self.depth > Self::ITEM_DEPTH {
+                self.current.push(byte);
+            }
+            if inside && self.depth == Self::ITEM_DEPTH {
+                items.push(mem::take(&mut self.current));
+            }
+        }
+        items
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::{Method, Url};
+
+use crate::routes::template;
+
+/// Upper bounds of the histogram buckets, in milliseconds; slower requests land in a final overflow bucket
+const BUCKETS_MS: [u64; 8] = [25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Latency histogram of a single route
+#[derive(Debug, Clone, Default)]
+pub struct RouteLatency {
+    count: u64,
+    total: Duration,
+    max: Duration,
+    buckets: [u64; BUCKETS_MS.len() + 1],
+}
+impl RouteLatency {
+    fn record(&mut self, latency: Duration) {
+        self.count += 1;
+        self.total += latency;
+        self.max = self.max.max(latency);
+        let bucket = BUCKETS_MS
+            .iter()
+            .position(|&bound| latency <= Duration::from_millis(bound))
+            .unwrap_or(BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+    }
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.count) {
+            Ok(0) => Duration::ZERO,
+            Ok(count) => self.total / count,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.count as f64),
+        }
+    }
+    /// Number of requests per bucket, as `(upper bound, count)`; the last bucket has no upper bound
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        BUCKETS_MS
+            .iter()
+            .map(|&bound| Some(Duration::from_millis(bound)))
+            .chain([None])
+            .zip(self.buckets.iter().copied())
+    }
+    /// Estimated latency under which `quantile` (0.0 to 1.0) of the requests completed, as a bucket bound
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        let target = (self.count as f64 * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bound, count) in self.buckets() {
+            seen += count;
+            if seen >= target {
+                return Some(bound.unwrap_or(self.max));
+            }
+        }
+        None
+    }
+}
+
+/// Snapshot of the request latencies, keyed by route (e.g. `GET /channels/:id/messages`)
+#[derive(Debug, Clone, Default)]
+pub struct LatencyReport {
+    routes: BTreeMap<String, RouteLatency>,
+}
+impl LatencyReport {
+    pub fn routes(&self) -> impl Iterator<Item = (&str, &RouteLatency)> {
+        self.routes
+            .iter()
+            .map(|(route, latency)| (route.as_str(), latency))
+    }
+    pub fn route(&self, route: &str) -> Option<&RouteLatency> {
+        self.routes.get(route)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Latencies {
+    routes: Arc<Mutex<HashMap<String, RouteLatency>>>,
+}
+impl Latencies {
+    pub(crate) fn record(&self, method: &Method, url: &Url, latency: Duration) {
+        // All requests to an endpoint share a histogram
+        let route = format!("{method} {}", template(url));
+        let mut routes = self.routes.lock().unwrap();
+        routes.entry(route).or_default().record(latency);
+    }
+    pub(crate) fn report(&self) -> LatencyReport {
+        let routes = self.routes.lock().unwrap();
+        LatencyReport {
+            routes: routes
+                .iter()
+                .map(|(route, latency)| (route.clone(), latency.clone()))
+                .collect(),
+        }
+    }
+}
@@ -1,55 +1,157 @@
-use bans::{DeleteServerBanRequest, GetServerBanRequest, GetServerBansRequest, ServerBanRequest};
+use assets::DownloadRequest;
+use audit::AuditSink;
+use bans::{
+    BanUsersRequest, DeleteServerBanRequest, GetServerBanRequest, GetServerBansRequest,
+    ServerBanRequest,
+};
+use batch::Batch;
+#[cfg(feature = "calendar")]
+use calendar::{BulkRsvpRequest, CalendarEventId, RsvpStatus};
 use channel::{
     ChannelId, ChannelType, CreateChannelRequest, DeleteChannelRequest, GetChannelRequest,
+    UpdateChannelRequest,
 };
+use chrono::{DateTime, TimeZone};
+use clock::{Clock, TokioClock};
+use config::GuildedConfig;
+#[cfg(feature = "docs")]
 use docs::{
     CreateDocRequest, DeleteDocRequest, DocId, GetDocRequest, GetDocsRequest, UpdateDocRequest,
 };
+use error::Error;
+use export::ExportMessagesRequest;
+#[cfg(feature = "forums")]
 use forums::CreateThreadRequest;
 use groups::{AddGroupMemberRequest, DeleteGroupMemberRequest, GroupId};
+use http::{Http, RetryPolicy};
+use inflight::Inflight;
+#[cfg(feature = "list")]
 use list::{
     CompleteListItemRequest, CreateListItemRequest, DeleteListItemRequest, GetListItemRequest,
     GetListItemsRequest, ListId, UncompleteListItemRequest, UpdateListItemRequest,
 };
+use media::{CreateUrlSignaturesRequest, UploadMediaRequest};
 use member::{
-    DeleteNicknameRequest, GetMemberRequest, GetMembersRequest, KickMemberRequest, ServerId,
-    UpdateNicknameRequest, UserId,
+    DeleteNicknameRequest, DetailedMembersRequest, GetMemberRequest, GetMembersRequest,
+    KickMemberRequest, ServerId, SetNicknameRequest, UpdateNicknameRequest, UserId,
 };
 use message::{
     CreateMessageRequest, DeleteMessageRequest, GetChannelMessagesRequest, GetMessageRequest,
     MessageId, UpdateMessageRequest,
 };
+use metrics::MetricsSink;
+use outbox::{Outbox, OutboxStore};
+use ping::PingRequest;
+use queue::Throttle;
+use raw::RawRequest;
 use reactions::{AddReactionRequest, ContentId, EmoteId};
-use reqwest::header::{self, HeaderMap, InvalidHeaderValue};
-use reqwest::Client;
-use roles::{GetMemberRolesRequest, RoleId};
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Client, ClientBuilder, Method, Proxy};
+use roles::GetMemberRolesRequest;
+#[cfg(feature = "xp")]
+use roles::RoleId;
+use routes::ApiVersion;
+use schedule::ScheduledTask;
+#[cfg(feature = "social")]
+use social::{GetSocialLinksRequest, MemberSocialLinksRequest, SocialMediaType};
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::future::Future;
 use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "xp")]
 use xp::{MemberXpRequest, RoleXpRequest};
 
+#[macro_use]
+mod macros;
+
+pub mod assets;
+pub mod audit;
 pub mod bans;
+pub mod batch;
+#[cfg(feature = "calendar")]
+pub mod calendar;
+pub mod cdn;
 pub mod channel;
+pub mod clock;
+pub mod config;
+#[cfg(feature = "debug-body")]
+mod debug_body;
+#[cfg(feature = "docs")]
 pub mod docs;
+pub mod dry_run;
+pub mod emotes;
 pub mod error;
+pub mod events;
+pub mod export;
+#[cfg(feature = "test-utils")]
+pub mod fixtures;
+pub mod format;
+#[cfg(feature = "forums")]
 pub mod forums;
 pub mod groups;
+pub mod http;
+mod id;
+mod inflight;
+mod json_items;
+pub mod latency;
+pub mod links;
+#[cfg(feature = "list")]
 pub mod list;
+pub mod media;
 pub mod member;
+pub mod mentions;
 pub mod message;
+pub mod meta;
+pub mod metrics;
+#[cfg(feature = "otel")]
+mod otel;
+pub mod outbox;
+pub mod ping;
+mod queue;
+pub mod raw;
 pub mod reactions;
 pub mod roles;
+pub mod routes;
+pub mod schedule;
+pub mod server;
+#[cfg(feature = "social")]
 pub mod social;
+pub mod streams;
+#[cfg(feature = "time")]
+pub mod timestamp;
+#[cfg(feature = "xp")]
 pub mod xp;
 
-static API_BASE: &str = "https://www.guilded.gg/api/v1";
+static API_HOST: &str = "https://www.guilded.gg";
+pub(crate) static TOKEN_VAR: &str = "GUILDED_TOKEN";
+pub(crate) static TOKEN_PREFIX: &str = "gapi_";
 
 #[derive(Debug, Clone)]
-pub struct GuildedClient(Client);
+pub struct GuildedClient {
+    client: Http,
+    /// Set when identical concurrent lookups should share a single call
+    inflight: Option<Inflight>,
+}
 impl GuildedClient {
-    pub fn new(token: &str) -> Result<Self, InvalidHeaderValue> {
-        let mut hm = HeaderMap::new();
-        hm.insert(header::AUTHORIZATION, format!("Bearer {token}").parse()?);
-        let client = Client::builder().default_headers(hm).build().unwrap();
-        Ok(Self(client))
+    pub fn new(token: &str) -> error::Result<Self> {
+        Self::builder(token).build()
+    }
+    pub fn builder(token: &str) -> GuildedClientBuilder {
+        GuildedClientBuilder::new(token)
+    }
+    /// Creates a client with the bot token stored in the `GUILDED_TOKEN` environment variable
+    pub fn from_env() -> error::Result<Self> {
+        Self::from_config(&GuildedConfig::default())
+    }
+    /// Creates a client with the settings of `config`, e.g. read from a configuration file
+    pub fn from_config(config: &GuildedConfig) -> error::Result<Self> {
+        config.builder()?.build()
+    }
+    /// Checks that the API is reachable and the token is valid
+    pub fn ping(&self) -> PingRequest {
+        PingRequest::new(self.client.clone())
     }
     pub fn create_channel<'a>(
         &self,
@@ -57,48 +159,80 @@ impl GuildedClient {
         name: &'a str,
         channel_type: ChannelType,
     ) -> CreateChannelRequest<'a> {
-        CreateChannelRequest::new(self.0.clone(), server, name, channel_type)
+        CreateChannelRequest::new(self.client.clone(), server, name, channel_type)
     }
     pub fn get_channel<'a>(&self, id: &'a ChannelId) -> GetChannelRequest<'a> {
-        GetChannelRequest::new(self.0.clone(), id)
+        GetChannelRequest::new(self.client.clone(), id).inflight(self.inflight.clone())
+    }
+    pub fn update_channel<'a>(&self, id: &'a ChannelId) -> UpdateChannelRequest<'a> {
+        UpdateChannelRequest::new(self.client.clone(), id)
     }
     pub fn delete_channel<'a>(&self, id: &'a ChannelId) -> DeleteChannelRequest<'a> {
-        DeleteChannelRequest::new(self.0.clone(), id)
+        DeleteChannelRequest::new(self.client.clone(), id)
     }
-    pub fn get_channels(&self) -> GetChannelRequest {
+    pub fn get_channels(&self) -> GetChannelRequest<'_> {
         unimplemented!()
     }
     pub fn send_message<'a>(
         &self,
         channel: &'a ChannelId,
-        content: &'a str,
+        content: impl Into<Cow<'a, str>>,
     ) -> CreateMessageRequest<'a> {
-        CreateMessageRequest::new(self.0.clone(), channel, content)
+        CreateMessageRequest::new(self.client.clone(), channel, content)
+    }
+    /// Starts a batch of requests sent a few at a time, e.g. for a sync job mixing role and channel changes
+    pub fn batch<'a, T: Send + 'a>(&self) -> Batch<'a, T> {
+        Batch::new()
+    }
+    /// Creates a queue that holds messages back while the API is unreachable, see [`Outbox`]
+    pub fn outbox(&self, store: impl OutboxStore + 'static) -> Outbox {
+        Outbox::new(self.clone(), store)
     }
     pub fn get_messages<'a>(&self, channel: &'a ChannelId) -> GetChannelMessagesRequest<'a> {
-        GetChannelMessagesRequest::new(self.0.clone(), channel)
+        GetChannelMessagesRequest::new(self.client.clone(), channel)
+    }
+    /// Streams the messages of a channel sent from `start` to `end`, newest first
+    pub fn messages_between<'a, T: TimeZone>(
+        &self,
+        channel: &'a ChannelId,
+        start: DateTime<T>,
+        end: DateTime<T>,
+    ) -> GetChannelMessagesRequest<'a> {
+        GetChannelMessagesRequest::new(self.client.clone(), channel).between(start, end)
+    }
+    /// Writes the whole history of a channel to a file or other writer
+    pub fn export_messages<'a>(&self, channel: &'a ChannelId) -> ExportMessagesRequest<'a> {
+        ExportMessagesRequest::new(self.client.clone(), channel)
     }
     pub fn get_message<'a>(
         &self,
         channel: &'a ChannelId,
         message: &'a MessageId,
     ) -> GetMessageRequest<'a> {
-        GetMessageRequest::new(self.0.clone(), channel, message)
+        GetMessageRequest::new(self.client.clone(), channel, message)
     }
     pub fn update_message<'a>(
         &self,
         channel: &'a ChannelId,
         message: &'a MessageId,
-        content: &'a str,
+        content: impl Into<Cow<'a, str>>,
     ) -> UpdateMessageRequest<'a> {
-        UpdateMessageRequest::new(self.0.clone(), channel, message, content)
+        UpdateMessageRequest::new(self.client.clone(), channel, message, content)
+    }
+    /// Edits a message without setting its content, e.g. to only replace or clear its embeds
+    pub fn edit_message<'a>(
+        &self,
+        channel: &'a ChannelId,
+        message: &'a MessageId,
+    ) -> UpdateMessageRequest<'a> {
+        UpdateMessageRequest::edit(self.client.clone(), channel, message)
     }
     pub fn delete_message<'a>(
         &self,
         channel: &'a ChannelId,
         message: &'a MessageId,
     ) -> DeleteMessageRequest<'a> {
-        DeleteMessageRequest::new(self.0.clone(), channel, message)
+        DeleteMessageRequest::new(self.client.clone(), channel, message)
     }
     pub fn update_nickname<'a>(
         &self,
@@ -106,119 +240,171 @@ impl GuildedClient {
         user: &'a UserId,
         nickname: &'a str,
     ) -> UpdateNicknameRequest<'a> {
-        UpdateNicknameRequest::new(self.0.clone(), server, user, nickname)
+        UpdateNicknameRequest::new(self.client.clone(), server, user, nickname)
     }
     pub fn delete_nickname<'a>(
         &self,
         server: &'a ServerId,
         user: &'a UserId,
     ) -> DeleteNicknameRequest<'a> {
-        DeleteNicknameRequest::new(self.0.clone(), server, user)
+        DeleteNicknameRequest::new(self.client.clone(), server, user)
+    }
+    /// Sets the member's nickname, or clears it when `nickname` is `None`
+    pub fn set_nickname<'a>(
+        &self,
+        server: &'a ServerId,
+        user: &'a UserId,
+        nickname: Option<&'a str>,
+    ) -> SetNicknameRequest<'a> {
+        SetNicknameRequest::new(self.client.clone(), server, user, nickname)
     }
     pub fn get_member<'a>(&self, server: &'a ServerId, user: &'a UserId) -> GetMemberRequest<'a> {
-        GetMemberRequest::new(self.0.clone(), server, user)
+        GetMemberRequest::new(self.client.clone(), server, user).inflight(self.inflight.clone())
     }
     pub fn kick_member<'a>(&self, server: &'a ServerId, user: &'a UserId) -> KickMemberRequest<'a> {
-        KickMemberRequest::new(self.0.clone(), server, user)
+        KickMemberRequest::new(self.client.clone(), server, user)
     }
     pub fn get_members<'a>(&self, server: &'a ServerId) -> GetMembersRequest<'a> {
-        GetMembersRequest::new(self.0.clone(), server)
+        GetMembersRequest::new(self.client.clone(), server)
+    }
+    /// Fetches the full record of every member of the server, rather than the summaries of [`GuildedClient::get_members`]
+    pub fn get_members_detailed<'a>(&self, server: &'a ServerId) -> DetailedMembersRequest<'a> {
+        DetailedMembersRequest::new(self.client.clone(), server)
+    }
+    #[cfg(feature = "social")]
+    pub fn get_social_link<'a>(
+        &self,
+        server: &'a ServerId,
+        user: &'a UserId,
+        link_type: SocialMediaType,
+    ) -> GetSocialLinksRequest<'a> {
+        GetSocialLinksRequest::new(self.client.clone(), server, user, link_type)
+    }
+    /// Fetches the `link_type` social link of every member of the server
+    #[cfg(feature = "social")]
+    pub fn get_members_social_links<'a>(
+        &self,
+        server: &'a ServerId,
+        link_type: SocialMediaType,
+    ) -> MemberSocialLinksRequest<'a> {
+        MemberSocialLinksRequest::new(self.client.clone(), server, link_type)
     }
     pub fn ban_user<'a>(&self, server: &'a ServerId, user: &'a UserId) -> ServerBanRequest<'a> {
-        ServerBanRequest::new(self.0.clone(), server, user)
+        ServerBanRequest::new(self.client.clone(), server, user)
+    }
+    pub fn ban_users<'a>(&self, server: &'a ServerId, users: &'a [UserId]) -> BanUsersRequest<'a> {
+        BanUsersRequest::new(self.client.clone(), server, users)
     }
     pub fn get_ban<'a>(&self, server: &'a ServerId, user: &'a UserId) -> GetServerBanRequest<'a> {
-        GetServerBanRequest::new(self.0.clone(), server, user)
+        GetServerBanRequest::new(self.client.clone(), server, user)
     }
     pub fn delete_ban<'a>(
         &self,
         server: &'a ServerId,
         user: &'a UserId,
     ) -> DeleteServerBanRequest<'a> {
-        DeleteServerBanRequest::new(self.0.clone(), server, user)
+        DeleteServerBanRequest::new(self.client.clone(), server, user)
     }
     pub fn get_bans<'a>(&self, server: &'a ServerId) -> GetServerBansRequest<'a> {
-        GetServerBansRequest::new(self.0.clone(), server)
+        GetServerBansRequest::new(self.client.clone(), server)
     }
+    #[cfg(feature = "forums")]
     pub fn create_thread<'a>(
         &self,
         channel: &'a ChannelId,
         title: &'a str,
         content: &'a str,
     ) -> CreateThreadRequest<'a> {
-        CreateThreadRequest::new(self.0.clone(), channel, title, content)
+        CreateThreadRequest::new(self.client.clone(), channel, title, content)
     }
+    #[cfg(feature = "list")]
     pub fn create_list_item<'a>(
         &self,
         channel: &'a ChannelId,
         message: &'a str,
     ) -> CreateListItemRequest<'a> {
-        CreateListItemRequest::new(self.0.clone(), channel, message)
+        CreateListItemRequest::new(self.client.clone(), channel, message)
     }
+    #[cfg(feature = "list")]
     pub fn get_list_items<'a>(&self, channel: &'a ChannelId) -> GetListItemsRequest<'a> {
-        GetListItemsRequest::new(self.0.clone(), channel)
+        GetListItemsRequest::new(self.client.clone(), channel)
     }
+    #[cfg(feature = "list")]
     pub fn get_list_item<'a>(
         &self,
         channel: &'a ChannelId,
         item: &'a ListId,
     ) -> GetListItemRequest<'a> {
-        GetListItemRequest::new(self.0.clone(), channel, item)
+        GetListItemRequest::new(self.client.clone(), channel, item)
     }
+    #[cfg(feature = "list")]
     pub fn update_list_item<'a>(
         &self,
         channel: &'a ChannelId,
         item: &'a ListId,
         message: &'a str,
     ) -> UpdateListItemRequest<'a> {
-        UpdateListItemRequest::new(self.0.clone(), channel, item, message)
+        UpdateListItemRequest::new(self.client.clone(), channel, item, message)
     }
+    #[cfg(feature = "list")]
     pub fn delete_list_item<'a>(
         &self,
         channel: &'a ChannelId,
         item: &'a ListId,
     ) -> DeleteListItemRequest<'a> {
-        DeleteListItemRequest::new(self.0.clone(), channel, item)
+        DeleteListItemRequest::new(self.client.clone(), channel, item)
     }
+    #[cfg(feature = "list")]
     pub fn complete_list_item<'a>(
         &self,
         channel: &'a ChannelId,
         item: &'a ListId,
     ) -> CompleteListItemRequest<'a> {
-        CompleteListItemRequest::new(self.0.clone(), channel, item)
+        CompleteListItemRequest::new(self.client.clone(), channel, item)
     }
+    #[cfg(feature = "list")]
     pub fn uncomplete_list_item<'a>(
         &self,
         channel: &'a ChannelId,
         item: &'a ListId,
     ) -> UncompleteListItemRequest<'a> {
-        UncompleteListItemRequest::new(self.0.clone(), channel, item)
+        UncompleteListItemRequest::new(self.client.clone(), channel, item)
     }
+    #[cfg(feature = "docs")]
     pub fn create_doc<'a>(
         &self,
         channel: &'a ChannelId,
         title: &'a str,
         content: &'a str,
     ) -> CreateDocRequest<'a> {
-        CreateDocRequest::new(self.0.clone(), channel, title, content)
+        CreateDocRequest::new(self.client.clone(), channel, title, content)
     }
+    #[cfg(feature = "docs")]
     pub fn get_docs<'a>(&self, channel: &'a ChannelId) -> GetDocsRequest<'a> {
-        GetDocsRequest::new(self.0.clone(), channel)
+        GetDocsRequest::new(self.client.clone(), channel)
     }
+    #[cfg(feature = "docs")]
     pub fn get_doc<'a>(&self, channel: &'a ChannelId, doc: &'a DocId) -> GetDocRequest<'a> {
-        GetDocRequest::new(self.0.clone(), channel, doc)
+        GetDocRequest::new(self.client.clone(), channel, doc)
     }
-    pub fn update_doc<'a>(
-        &self,
-        channel: &'a ChannelId,
-        doc: &'a DocId,
-        title: &'a str,
-        content: &'a str,
-    ) -> UpdateDocRequest<'a> {
-        UpdateDocRequest::new(self.0.clone(), channel, doc, title, content)
+    #[cfg(feature = "docs")]
+    pub fn update_doc<'a>(&self, channel: &'a ChannelId, doc: &'a DocId) -> UpdateDocRequest<'a> {
+        UpdateDocRequest::new(self.client.clone(), channel, doc)
     }
+    #[cfg(feature = "docs")]
     pub fn delete_doc<'a>(&self, channel: &'a ChannelId, doc: &'a DocId) -> DeleteDocRequest<'a> {
-        DeleteDocRequest::new(self.0.clone(), channel, doc)
+        DeleteDocRequest::new(self.client.clone(), channel, doc)
+    }
+    /// Sets the RSVP of all `users` to a calendar event at once
+    #[cfg(feature = "calendar")]
+    pub fn bulk_rsvp<'a>(
+        &self,
+        channel: &'a ChannelId,
+        event: &'a CalendarEventId,
+        users: &'a [UserId],
+        status: RsvpStatus,
+    ) -> BulkRsvpRequest<'a> {
+        BulkRsvpRequest::new(self.client.clone(), channel, event, users, status)
     }
     pub fn add_reaction<'a, C: Into<ContentId<'a>>>(
         &self,
@@ -226,50 +412,316 @@ impl GuildedClient {
         content: C,
         emote: &'a EmoteId,
     ) -> AddReactionRequest<'a> {
-        AddReactionRequest::new(self.0.clone(), channel, content, emote)
+        AddReactionRequest::new(self.client.clone(), channel, content, emote)
     }
+    #[cfg(feature = "xp")]
     pub fn award_member<'a>(
         &self,
         server: &'a ServerId,
         user: &'a UserId,
         amount: i32,
     ) -> MemberXpRequest<'a> {
-        MemberXpRequest::new(self.0.clone(), server, user, amount)
+        MemberXpRequest::new(self.client.clone(), server, user, amount)
     }
+    #[cfg(feature = "xp")]
     pub fn award_role<'a>(
         &self,
         server: &'a ServerId,
         role: &'a RoleId,
         amount: i32,
     ) -> RoleXpRequest<'a> {
-        RoleXpRequest::new(self.0.clone(), server, role, amount)
+        RoleXpRequest::new(self.client.clone(), server, role, amount)
     }
     pub fn add_group_member<'a>(
         &self,
         group: &'a GroupId,
         user: &'a UserId,
     ) -> AddGroupMemberRequest<'a> {
-        AddGroupMemberRequest::new(self.0.clone(), group, user)
+        AddGroupMemberRequest::new(self.client.clone(), group, user)
     }
     pub fn delete_group_member<'a>(
         &self,
         group: &'a GroupId,
         user: &'a UserId,
     ) -> DeleteGroupMemberRequest<'a> {
-        DeleteGroupMemberRequest::new(self.0.clone(), group, user)
+        DeleteGroupMemberRequest::new(self.client.clone(), group, user)
     }
     pub fn get_member_roles<'a>(
         &self,
         server: &'a ServerId,
         user: &'a UserId,
     ) -> GetMemberRolesRequest<'a> {
-        GetMemberRolesRequest::new(self.0.clone(), server, user)
+        GetMemberRolesRequest::new(self.client.clone(), server, user)
+    }
+    /// Downloads an asset (avatar, banner, attachment, ...) from a URL.
+    /// The bot token is not sent with the request.
+    pub fn download<'a>(&self, url: &'a str) -> DownloadRequest<'a> {
+        DownloadRequest::new(self.client.assets().clone(), url)
+    }
+    /// Uploads an image to Guilded's CDN, for use in embeds.
+    /// The bot token is not sent with the request, see [`UploadMediaRequest`].
+    pub fn upload_media<'a>(&self, file_name: &'a str, data: Vec<u8>) -> UploadMediaRequest<'a> {
+        UploadMediaRequest::new(self.client.clone(), file_name, data)
+    }
+    /// Gets signed versions of CDN URLs, which are needed to fetch user uploaded media
+    pub fn sign_urls<'a>(&self, urls: &[&'a str]) -> CreateUrlSignaturesRequest<'a> {
+        CreateUrlSignaturesRequest::new(self.client.clone(), urls)
+    }
+    /// Builds a request to any API route, relative to the API base (e.g. `/users/@me`).
+    /// Authentication and error handling are the same as for the wrapped endpoints.
+    pub fn request<'a>(&self, method: Method, path: &'a str) -> RawRequest<'a> {
+        RawRequest::new(self.client.clone(), method, path)
+    }
+    /// Runs `task` every `period`, starting one period from now.
+    /// Each run receives a clone of this client.
+    pub fn every<F, Fut>(&self, period: Duration, task: F) -> ScheduledTask
+    where
+        F: FnMut(GuildedClient) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        ScheduledTask::every(self.clone(), period, task)
+    }
+    /// Runs `task` on a cron schedule (`sec min hour day-of-month month day-of-week [year]`, in UTC).
+    ///
+    /// Times that pass while a run is still going are skipped, so a slow run isn't followed by a burst.
+    #[cfg(feature = "cron")]
+    pub fn cron<F, Fut>(&self, expression: &str, task: F) -> error::Result<ScheduledTask>
+    where
+        F: FnMut(GuildedClient) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let schedule = expression.parse()?;
+        Ok(ScheduledTask::cron(self.clone(), schedule, task))
+    }
+}
+type Configure = Box<dyn Fn(ClientBuilder) -> ClientBuilder + Send + Sync>;
+
+pub struct GuildedClientBuilder {
+    token: String,
+    /// Settings applied to both the API and the asset client
+    config: Vec<Configure>,
+    coalesce: bool,
+    audit: Option<Arc<dyn AuditSink>>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    ordered_sends: bool,
+    throttle: Option<Throttle>,
+    retry: RetryPolicy,
+    clock: Option<Arc<dyn Clock>>,
+    base_url: Option<String>,
+    api_version: ApiVersion,
+}
+impl GuildedClientBuilder {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_owned(),
+            config: Vec::new(),
+            coalesce: false,
+            audit: None,
+            metrics: None,
+            ordered_sends: false,
+            throttle: None,
+            retry: RetryPolicy::default(),
+            clock: None,
+            base_url: None,
+            api_version: ApiVersion::default(),
+        }
+    }
+    pub fn build(self) -> error::Result<GuildedClient> {
+        validate_token(&self.token)?;
+        let mut auth: HeaderValue = format!("Bearer {}", self.token).parse().map_err(|_| {
+            Error::InvalidToken("token contains characters not allowed in a header")
+        })?;
+        // Keeps the token out of Debug output
+        auth.set_sensitive(true);
+        let mut hm = HeaderMap::new();
+        hm.insert(header::AUTHORIZATION, auth);
+        let configure = |builder| self.config.iter().fold(builder, |builder, f| f(builder));
+        let client = configure(Client::builder()).default_headers(hm).build()?;
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.clone(),
+            None => self.api_version.base_url(),
+        };
+        let assets = configure(Client::builder()).build()?;
+        let clock = self.clock.unwrap_or_else(|| Arc::new(TokioClock));
+        let client = Http::new(client, assets)
+            .base_url(&base_url)
+            .audit(self.audit)
+            .metrics(self.metrics)
+            .ordered_sends(self.ordered_sends)
+            .throttle_sends(self.throttle.map(|throttle| throttle.clock(clock.clone())))
+            .retry_policy(self.retry)
+            .clock(clock);
+        let inflight = self.coalesce.then(Inflight::default);
+        Ok(GuildedClient { client, inflight })
+    }
+    fn configure(
+        mut self,
+        f: impl Fn(ClientBuilder) -> ClientBuilder + Send + Sync + 'static,
+    ) -> Self {
+        self.config.push(Box::new(f));
+        self
+    }
+    /// Whether concurrent lookups of the same member or channel share a single request (default: disabled).
+    ///
+    /// Useful when many tasks look up the same hot entities at once.
+    pub fn coalesce_requests(mut self, enable: bool) -> Self {
+        self.coalesce = enable;
+        self
+    }
+    /// Sends requests to `base_url` instead of `https://www.guilded.gg/api/v1`, e.g. a mock server in tests
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_owned());
+        self
+    }
+    /// Version of the API to send requests to (default: v1).
+    ///
+    /// Overridden by [`GuildedClientBuilder::base_url`], which already includes the version.
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = version;
+        self
+    }
+    /// Records every change made through the client (bans, kicks, messages, ...) in `sink`
+    pub fn audit(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit = Some(Arc::new(sink));
+        self
+    }
+    /// Reports request counts, errors, latencies and rate limit waits to `sink`.
+    ///
+    /// With the `prometheus` feature, [`PrometheusSink`](metrics::PrometheusSink) exports them to a Prometheus registry.
+    pub fn metrics(mut self, sink: impl MetricsSink + 'static) -> Self {
+        self.metrics = Some(Arc::new(sink));
+        self
+    }
+    /// Whether messages to the same channel are sent one after the other, in the order they were sent in,
+    /// even when sent from concurrent tasks (default: disabled). Different channels still send in parallel.
+    pub fn ordered_sends(mut self, enable: bool) -> Self {
+        self.ordered_sends = enable;
+        self
+    }
+    /// Sends at most `max` messages to a channel every `period`; further messages wait for their turn.
+    ///
+    /// With `coalesce`, plain text messages that have to wait are merged into a single message, one per line.
+    /// Protects against spamming a channel when a burst of events triggers a message each.
+    pub fn throttle_sends(mut self, max: usize, period: Duration, coalesce: bool) -> Self {
+        self.throttle = Some(Throttle::new(max, period, coalesce));
+        self
+    }
+    /// How many times a rate limited request is retried before the error is returned (default: 5)
+    pub fn rate_limit_retries(mut self, retries: u32) -> Self {
+        self.retry.retries = retries;
+        self
+    }
+    /// Wait before retrying a rate limited request when the server doesn't say how long to wait (default: 1 second)
+    pub fn default_retry_after(mut self, wait: Duration) -> Self {
+        self.retry.default_wait = wait;
+        self
+    }
+    /// Clock the rate limit and retry waits are timed with (default: [`TokioClock`](clock::TokioClock))
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+    /// Gives up on requests that haven't completed after `timeout` (default: no timeout)
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.configure(move |builder| builder.timeout(timeout))
+    }
+    /// Gives up on connecting to the server after `timeout` (default: no timeout)
+    pub fn connect_timeout(self, timeout: Duration) -> Self {
+        self.configure(move |builder| builder.connect_timeout(timeout))
+    }
+    /// Routes requests through `proxy`.
+    ///
+    /// HTTP(S) proxies work out of the box; `socks5://` and `socks5h://` proxies need the `socks` feature.
+    /// Credentials can be attached with [`Proxy::basic_auth`] or given in the proxy URL.
+    pub fn proxy(self, proxy: Proxy) -> Self {
+        self.configure(move |builder| builder.proxy(proxy.clone()))
+    }
+    /// Ignores proxies configured through the environment (`HTTP_PROXY`, `HTTPS_PROXY`, ...).
+    /// This also clears proxies added with [`GuildedClientBuilder::proxy`] so far, so call it first.
+    pub fn no_proxy(self) -> Self {
+        self.configure(ClientBuilder::no_proxy)
+    }
+    /// Whether to ask for gzip compressed responses (default: enabled)
+    #[cfg(feature = "gzip")]
+    pub fn gzip(self, enable: bool) -> Self {
+        self.configure(move |builder| builder.gzip(enable))
+    }
+    /// Whether to ask for brotli compressed responses (default: enabled)
+    #[cfg(feature = "brotli")]
+    pub fn brotli(self, enable: bool) -> Self {
+        self.configure(move |builder| builder.brotli(enable))
+    }
+    /// Maximum number of idle connections kept open to the API (default: unlimited)
+    pub fn pool_max_idle_per_host(self, max: usize) -> Self {
+        self.configure(move |builder| builder.pool_max_idle_per_host(max))
+    }
+    /// How long an idle connection is kept in the pool; `None` keeps them forever (default: 90 seconds)
+    pub fn pool_idle_timeout(self, timeout: Option<Duration>) -> Self {
+        self.configure(move |builder| builder.pool_idle_timeout(timeout))
+    }
+    /// Sends TCP keepalive probes at the given interval (default: disabled)
+    pub fn tcp_keepalive(self, interval: Option<Duration>) -> Self {
+        self.configure(move |builder| builder.tcp_keepalive(interval))
+    }
+    /// Only speak HTTP/2, skipping protocol negotiation
+    pub fn http2_prior_knowledge(self) -> Self {
+        self.configure(ClientBuilder::http2_prior_knowledge)
+    }
+    /// Sends HTTP/2 keepalive pings at the given interval (default: disabled)
+    pub fn http2_keep_alive_interval(self, interval: Option<Duration>) -> Self {
+        self.configure(move |builder| builder.http2_keep_alive_interval(interval))
+    }
+    /// How long to wait for a keepalive ping to be acknowledged before closing the connection
+    pub fn http2_keep_alive_timeout(self, timeout: Duration) -> Self {
+        self.configure(move |builder| builder.http2_keep_alive_timeout(timeout))
+    }
+    /// Whether keepalive pings are also sent while no requests are in flight
+    pub fn http2_keep_alive_while_idle(self, enabled: bool) -> Self {
+        self.configure(move |builder| builder.http2_keep_alive_while_idle(enabled))
     }
 }
+
+impl Debug for GuildedClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GuildedClientBuilder")
+            .field("token", &"<redacted>")
+            .field("config", &self.config.len())
+            .field("coalesce", &self.coalesce)
+            .field("audit", &self.audit.is_some())
+            .field("metrics", &self.metrics.is_some())
+            .field("ordered_sends", &self.ordered_sends)
+            .field("throttle", &self.throttle)
+            .field("retry", &self.retry)
+            .field("clock", &self.clock.is_some())
+            .finish()
+    }
+}
+
+/// Checks that `token` looks like a Guilded bot token (`gapi_` followed by base64)
+fn validate_token(token: &str) -> error::Result<()> {
+    let key = token
+        .strip_prefix(TOKEN_PREFIX)
+        .ok_or(Error::InvalidToken("bot tokens start with \"gapi_\""))?;
+    if key.is_empty() {
+        return Err(Error::InvalidToken(
+            "token is empty after the \"gapi_\" prefix",
+        ));
+    }
+    let valid = key
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=' | b'-' | b'_'));
+    if !valid {
+        return Err(Error::InvalidToken(
+            "token contains characters outside of base64",
+        ));
+    }
+    Ok(())
+}
+
 impl Deref for GuildedClient {
-    type Target = Client;
+    type Target = Http;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.client
     }
 }
@@ -0,0 +1,134 @@
+//! Links to channels and messages on guilded.gg, e.g. to accept a pasted message link as a
+//! command argument
+
+use std::fmt::Display;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use reqwest::Url;
+
+use crate::channel::ChannelId;
+use crate::groups::GroupId;
+use crate::member::ServerId;
+use crate::message::MessageId;
+
+/// How a link names its server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkServer {
+    /// `guilded.gg/teams/{id}/...`
+    Id(ServerId),
+    /// Vanity URL slug, `guilded.gg/{slug}/...`; the ID has to be looked up
+    Vanity(String),
+}
+
+/// Channel, or message in it, a guilded.gg link points to.
+///
+/// Parses links like `https://www.guilded.gg/{server}/groups/{group}/channels/{channel}/chat?messageId={message}`,
+/// where the server is either a vanity slug or `teams/{id}`, and formats back to the same shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelLink {
+    server: LinkServer,
+    group: Option<GroupId>,
+    channel: ChannelId,
+    message: Option<MessageId>,
+}
+impl ChannelLink {
+    pub fn new(server: &ServerId, group: &GroupId, channel: &ChannelId) -> Self {
+        Self {
+            server: LinkServer::Id(server.clone()),
+            group: Some(group.clone()),
+            channel: *channel,
+            message: None,
+        }
+    }
+    /// Links to `message` in the channel instead of the channel itself
+    pub fn message(mut self, message: &MessageId) -> Self {
+        self.message = Some(*message);
+        self
+    }
+    pub fn server(&self) -> &LinkServer {
+        &self.server
+    }
+    /// ID of the server, if the link has it rather than a vanity slug
+    pub fn server_id(&self) -> Option<&ServerId> {
+        match &self.server {
+            LinkServer::Id(server) => Some(server),
+            LinkServer::Vanity(_) => None,
+        }
+    }
+    pub fn group(&self) -> Option<&GroupId> {
+        self.group.as_ref()
+    }
+    pub fn channel(&self) -> &ChannelId {
+        &self.channel
+    }
+    pub fn message_id(&self) -> Option<&MessageId> {
+        self.message.as_ref()
+    }
+    pub fn url(&self) -> Url {
+        self.to_string()
+            .parse()
+            .expect("links are built from URL-safe IDs")
+    }
+}
+impl FromStr for ChannelLink {
+    type Err = ();
+
+    /// Parses a link to a channel or message, with or without `www.` and the scheme
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        let s = s.trim().trim_start_matches('<').trim_end_matches('>');
+        let url: Url = if s.contains("://") {
+            s.parse()
+        } else {
+            format!("https://{s}").parse()
+        }
+        .map_err(|_| ())?;
+        if !matches!(url.host_str(), Some("guilded.gg" | "www.guilded.gg")) {
+            return Err(());
+        }
+        let mut segments = url.path_segments().ok_or(())?.filter(|s| !s.is_empty());
+        let server = match segments.next().ok_or(())? {
+            "teams" => LinkServer::Id(segments.next().ok_or(())?.parse()?),
+            slug => LinkServer::Vanity(slug.to_owned()),
+        };
+        let mut next = segments.next().ok_or(())?;
+        let mut group = None;
+        if next == "groups" {
+            group = Some(segments.next().ok_or(())?.parse()?);
+            next = segments.next().ok_or(())?;
+        }
+        if next != "channels" {
+            return Err(());
+        }
+        let channel = segments.next().ok_or(())?.parse().map_err(|_| ())?;
+        let message = url
+            .query_pairs()
+            .find(|(name, _)| name == "messageId")
+            .map(|(_, message)| message.parse().map_err(|_| ()))
+            .transpose()?;
+
+        Ok(Self {
+            server,
+            group,
+            channel,
+            message,
+        })
+    }
+}
+impl Display for ChannelLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("https://www.guilded.gg/")?;
+        match &self.server {
+            LinkServer::Id(server) => write!(f, "teams/{server}")?,
+            LinkServer::Vanity(slug) => f.write_str(slug)?,
+        }
+        if let Some(group) = &self.group {
+            write!(f, "/groups/{group}")?;
+        }
+        write!(f, "/channels/{}/chat", self.channel)?;
+        if let Some(message) = &self.message {
+            write!(f, "?messageId={message}")?;
+        }
+        Ok(())
+    }
+}
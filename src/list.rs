@@ -5,16 +5,22 @@ use std::str::FromStr;
 
 use async_stream::stream;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
-use tokio_stream::Stream;
+use tokio::task::JoinSet;
+use tokio_stream::{Stream, StreamExt};
 use uuid::Uuid;
 
 use crate::channel::ChannelId;
-use crate::error::Result;
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
 use crate::member::{ServerId, UserId};
 use crate::message::WebhookId;
-use crate::API_BASE;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
+use crate::streams::ApiStream;
+use crate::GuildedClient;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 #[repr(transparent)]
@@ -108,6 +114,43 @@ pub struct ListItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     note: Option<ListNote>,
 }
+impl ListItem {
+    pub fn id(&self) -> &ListId {
+        &self.id
+    }
+    /// Item this one is nested under, if any
+    pub fn parent(&self) -> Option<&ListId> {
+        self.parent.as_ref()
+    }
+    pub fn is_completed(&self) -> bool {
+        self.completed.is_some()
+    }
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+    pub fn updated(&self) -> Option<&DateTime<Utc>> {
+        self.updated.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+    pub fn completed(&self) -> Option<&DateTime<Utc>> {
+        self.completed.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn completed_time(&self) -> Option<time::OffsetDateTime> {
+        self.completed
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -124,6 +167,24 @@ pub struct ListNote {
     updated_by: Option<UserId>,
     content: String,
 }
+impl ListNote {
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+    pub fn updated(&self) -> Option<&DateTime<Utc>> {
+        self.updated.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -158,6 +219,47 @@ pub struct ListItemSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     note: Option<ListNoteSummary>,
 }
+impl ListItemSummary {
+    pub fn id(&self) -> &ListId {
+        &self.id
+    }
+    /// Item this one is nested under, if any
+    pub fn parent(&self) -> Option<&ListId> {
+        self.parent.as_ref()
+    }
+    pub fn is_completed(&self) -> bool {
+        self.completed.is_some()
+    }
+    /// Fetches the full item, with the message and note content the summary leaves out
+    pub async fn fetch(&self, client: &GuildedClient) -> Result<ListItem> {
+        client.get_list_item(&self.channel, &self.id).send().await
+    }
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+    pub fn updated(&self) -> Option<&DateTime<Utc>> {
+        self.updated.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+    pub fn completed(&self) -> Option<&DateTime<Utc>> {
+        self.completed.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn completed_time(&self) -> Option<time::OffsetDateTime> {
+        self.completed
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -173,6 +275,24 @@ pub struct ListNoteSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     updated_by: Option<UserId>,
 }
+impl ListNoteSummary {
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+    pub fn updated(&self) -> Option<&DateTime<Utc>> {
+        self.updated.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+}
 
 #[derive(Debug, Serialize)]
 struct CreateListItemNoteBody<'a> {
@@ -188,17 +308,24 @@ struct CreateListItemBody<'a> {
     message: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
     note: Option<CreateListItemNoteBody<'a>>,
+    #[serde(rename = "parentListItemId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<&'a ListId>,
 }
 impl<'a> CreateListItemBody<'a> {
     pub fn new(message: &'a str) -> Self {
         Self {
             message,
             note: None,
+            parent: None,
         }
     }
     pub fn note(&mut self, note: &'a str) {
         self.note = Some(CreateListItemNoteBody::new(note))
     }
+    pub fn parent(&mut self, parent: &'a ListId) {
+        self.parent = Some(parent);
+    }
 }
 #[derive(Debug, Serialize, Deserialize)]
 struct CreateListItemResponse {
@@ -207,38 +334,62 @@ struct CreateListItemResponse {
 }
 #[derive(Debug)]
 pub struct CreateListItemRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     message: &'a str,
     note: Option<&'a str>,
+    parent: Option<&'a ListId>,
 }
 impl<'a> CreateListItemRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, message: &'a str) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, message: &'a str) -> Self {
         Self {
             client,
             channel,
             message,
             note: None,
+            parent: None,
         }
     }
     pub async fn send(self) -> Result<ListItem> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let mut body = CreateListItemBody::new(self.message);
         if let Some(note) = self.note {
             body.note(note);
         }
+        if let Some(parent) = self.parent {
+            body.parent(parent);
+        }
         let request = self
             .client
-            .post(format!("{API_BASE}/channels/{}/items", self.channel))
+            .post(Route::ListItems {
+                channel: self.channel,
+            })
             .json(&body)
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ListItem, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let item: CreateListItemResponse = response.json().await?;
-        Ok(item.item)
+        Ok((item.item, meta))
     }
     pub fn note(mut self, note: &'a str) -> Self {
         self.note = Some(note);
         self
     }
+    /// Nests the new item under an existing one
+    pub fn parent(mut self, parent: &'a ListId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -248,31 +399,156 @@ struct GetListItemsResponse {
     items: Vec<ListItemSummary>,
 }
 #[derive(Debug)]
-struct ListItemsStream;
-impl ListItemsStream {
-    fn iter(glir: GetListItemsRequest) -> impl Stream<Item = Result<ListItemSummary>> + '_ {
+struct ListItemsPages;
+impl ListItemsPages {
+    fn iter(glir: GetListItemsRequest<'_>) -> impl Stream<Item = Result<ListItemSummary>> + '_ {
         stream! {
-            let request = glir.client.get(format!("{API_BASE}/channels/{}/items", glir.channel)).build()?;
-            let response = glir.client.execute(request).await?.error_for_status()?;
+            let request = glir.client.get(Route::ListItems { channel: glir.channel }).build()?;
+            let response = glir.client.execute(request).await?.check_status()?;
             let items: GetListItemsResponse = response.json().await?;
 
             for item in items.items {
+                if glir.parent.is_some() && item.parent() != glir.parent {
+                    continue;
+                }
+                if glir.completed.is_some_and(|completed| item.is_completed() != completed) {
+                    continue;
+                }
                 yield Ok(item)
             }
         }
     }
 }
+/// Items of a list, see [`GetListItemsRequest::send`]
+pub type ListItemsStream<'a> = ApiStream<'a, ListItemSummary>;
+
 #[derive(Debug)]
 pub struct GetListItemsRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
+    parent: Option<&'a ListId>,
+    completed: Option<bool>,
 }
 impl<'a> GetListItemsRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId) -> Self {
-        Self { client, channel }
+    pub fn new(client: Http, channel: &'a ChannelId) -> Self {
+        Self {
+            client,
+            channel,
+            parent: None,
+            completed: None,
+        }
     }
-    pub fn send(self) -> impl Stream<Item = Result<ListItemSummary>> + 'a {
-        ListItemsStream::iter(self)
+    pub fn send(self) -> ListItemsStream<'a> {
+        ApiStream::new(ListItemsPages::iter(self))
+    }
+    /// Reads all the items into a `Vec`, for when they aren't needed one at a time
+    pub async fn collect_all(self) -> Result<Vec<ListItemSummary>> {
+        self.send().collect_all().await
+    }
+    /// Only yields the direct children of `parent`
+    pub fn children_of(mut self, parent: &'a ListId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+    /// Only yields items that have been completed. Filtered client-side.
+    pub fn completed_only(mut self) -> Self {
+        self.completed = Some(true);
+        self
+    }
+    /// Only yields items that are still outstanding. Filtered client-side.
+    pub fn incomplete_only(mut self) -> Self {
+        self.completed = Some(false);
+        self
+    }
+    /// Fetches the full items instead of their summaries, see [`ListItemSummary::fetch`]
+    pub fn full(self) -> GetFullListItemsRequest<'a> {
+        GetFullListItemsRequest::new(self)
+    }
+    /// Counts the matching items, split by completion state
+    pub async fn counts(self) -> Result<ListItemCounts> {
+        let mut counts = ListItemCounts::default();
+        let items = self.send();
+        tokio::pin!(items);
+        while let Some(item) = items.next().await {
+            if item?.is_completed() {
+                counts.completed += 1;
+            } else {
+                counts.incomplete += 1;
+            }
+        }
+        Ok(counts)
+    }
+}
+
+/// Number of list items in each completion state, see [`GetListItemsRequest::counts`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListItemCounts {
+    completed: usize,
+    incomplete: usize,
+}
+impl ListItemCounts {
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+    pub fn incomplete(&self) -> usize {
+        self.incomplete
+    }
+    pub fn total(&self) -> usize {
+        self.completed + self.incomplete
+    }
+}
+
+/// Full items of a list, see [`GetFullListItemsRequest::send`]
+pub type FullListItemsStream<'a> = ApiStream<'a, ListItem>;
+
+/// Full items of a list, fetched a few at a time from the summaries of [`GetListItemsRequest`]
+#[derive(Debug)]
+pub struct GetFullListItemsRequest<'a> {
+    items: GetListItemsRequest<'a>,
+    concurrency: usize,
+}
+impl<'a> GetFullListItemsRequest<'a> {
+    pub fn new(items: GetListItemsRequest<'a>) -> Self {
+        Self {
+            items,
+            concurrency: 4,
+        }
+    }
+    /// Yields each item, in the order the lookups complete
+    pub fn send(self) -> FullListItemsStream<'a> {
+        let client = self.items.client.clone();
+        let concurrency = self.concurrency.max(1);
+        ApiStream::new(stream! {
+            let summaries = self.items.send();
+            tokio::pin!(summaries);
+            let mut tasks = JoinSet::new();
+            let mut summaries_done = false;
+            loop {
+                while !summaries_done && tasks.len() < concurrency {
+                    let Some(summary) = summaries.next().await else {
+                        summaries_done = true;
+                        break;
+                    };
+                    let summary = summary?;
+                    let client = client.clone();
+                    tasks.spawn(async move {
+                        GetListItemRequest::new(client, &summary.channel, &summary.id)
+                            .send()
+                            .await
+                    });
+                }
+                let Some(joined) = tasks.join_next().await else {
+                    break;
+                };
+                // Tasks are never aborted and lookups don't panic
+                yield joined.expect("list item task failed");
+            }
+        })
+    }
+    /// How many items are fetched at the same time (default: 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
     }
 }
 
@@ -284,12 +560,12 @@ struct GetListItemResponse {
 }
 #[derive(Debug)]
 pub struct GetListItemRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     item: &'a ListId,
 }
 impl<'a> GetListItemRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, item: &'a ListId) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, item: &'a ListId) -> Self {
         Self {
             client,
             channel,
@@ -297,17 +573,34 @@ impl<'a> GetListItemRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<ListItem> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .get(format!(
-                "{API_BASE}/channels/{}/items/{}",
-                self.channel, self.item
-            ))
+            .get(Route::ListItem {
+                channel: self.channel,
+                item: self.item,
+            })
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ListItem, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self
+            .client
+            .execute(request)
+            .await?
+            .check_status()
+            .map_err(|e| e.not_found("list item", self.item))?;
+        let meta = ResponseMeta::new(&response);
         let item: GetListItemResponse = response.json().await?;
 
-        Ok(item.item)
+        Ok((item.item, meta))
     }
 }
 
@@ -345,14 +638,14 @@ impl<'a> UpdateListItemBody<'a> {
 }
 #[derive(Debug)]
 pub struct UpdateListItemRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     item: &'a ListId,
     message: &'a str,
     note: Option<&'a str>,
 }
 impl<'a> UpdateListItemRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, item: &'a ListId, message: &'a str) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, item: &'a ListId, message: &'a str) -> Self {
         Self {
             client,
             channel,
@@ -362,22 +655,34 @@ impl<'a> UpdateListItemRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<ListItem> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let mut body = UpdateListItemBody::new(self.message);
         if let Some(note) = self.note {
             body.note(note);
         }
         let request = self
             .client
-            .put(format!(
-                "{API_BASE}/channels/{}/items/{}",
-                self.channel, self.item
-            ))
+            .put(Route::ListItem {
+                channel: self.channel,
+                item: self.item,
+            })
             .json(&body)
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ListItem, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let item: UpdateListItemResponse = response.json().await?;
 
-        Ok(item.item)
+        Ok((item.item, meta))
     }
     pub fn note(mut self, note: &'a str) -> Self {
         self.note = Some(note);
@@ -385,86 +690,26 @@ impl<'a> UpdateListItemRequest<'a> {
     }
 }
 
-#[derive(Debug)]
-pub struct DeleteListItemRequest<'a> {
-    client: Client,
-    channel: &'a ChannelId,
-    item: &'a ListId,
-}
-impl<'a> DeleteListItemRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, item: &'a ListId) -> Self {
-        Self {
-            client,
-            channel,
-            item,
-        }
-    }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .delete(format!(
-                "{API_BASE}/channels/{}/items/{}",
-                self.channel, self.item
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
-
-        Ok(())
+request! {
+    pub struct DeleteListItemRequest<'a> {
+        channel: &'a ChannelId,
+        item: &'a ListId,
     }
+    DELETE ListItem => ()
 }
 
-#[derive(Debug)]
-pub struct CompleteListItemRequest<'a> {
-    client: Client,
-    channel: &'a ChannelId,
-    item: &'a ListId,
-}
-impl<'a> CompleteListItemRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, item: &'a ListId) -> Self {
-        Self {
-            client,
-            channel,
-            item,
-        }
+request! {
+    pub struct CompleteListItemRequest<'a> {
+        channel: &'a ChannelId,
+        item: &'a ListId,
     }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .post(format!(
-                "{API_BASE}/channels/{}/items/{}/complete",
-                self.channel, self.item
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
-
-        Ok(())
-    }
-}
-
-#[derive(Debug)]
-pub struct UncompleteListItemRequest<'a> {
-    client: Client,
-    channel: &'a ChannelId,
-    item: &'a ListId,
+    POST ListItemComplete => ()
 }
-impl<'a> UncompleteListItemRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, item: &'a ListId) -> Self {
-        Self {
-            client,
-            channel,
-            item,
-        }
-    }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .delete(format!(
-                "{API_BASE}/channels/{}/items/{}/complete",
-                self.channel, self.item
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
 
-        Ok(())
+request! {
+    pub struct UncompleteListItemRequest<'a> {
+        channel: &'a ChannelId,
+        item: &'a ListId,
     }
+    DELETE ListItemComplete => ()
 }
@@ -0,0 +1,170 @@
+/// Defines an enum over the string values of an API field.
+///
+/// Values Guilded adds later deserialize to `Unknown` instead of failing the whole response,
+/// and serialize back unchanged.
+macro_rules! api_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+        #[non_exhaustive]
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// Value this version of the crate doesn't know about
+            Unknown(String),
+        }
+        impl $name {
+            /// Value as sent by the API
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(value) => value,
+                }
+            }
+        }
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+        impl serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+                Ok(match value.as_str() {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown(value),
+                })
+            }
+        }
+    };
+}
+
+/// Defines a request to a single API route, with the usual `new`, setters, `send`,
+/// `send_with_meta` and `dry_run`.
+///
+/// Required fields are the route's parameters and must be named after them. Optional fields get a
+/// setter each and are sent as a camelCase JSON body, leaving out those that weren't set. The
+/// response is either `()`, or the field of a wrapper struct the body deserializes to:
+///
+/// ```ignore
+/// request! {
+///     pub struct UpdateThingRequest<'a> {
+///         channel: &'a ChannelId,
+///     }
+///     PATCH Channel
+///     optional UpdateThingBody {
+///         name: &'a str,
+///     }
+///     => UpdateThingResponse.thing: Thing
+/// }
+/// ```
+macro_rules! request {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident<'a> {
+            $($field:ident: $ty:ty,)*
+        }
+        $method:ident $route:ident
+        $(optional $body:ident {
+            $($(#[$opt_meta:meta])* $opt:ident: $opt_ty:ty,)*
+        })?
+        => $($output:tt)+
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        $vis struct $name<'a> {
+            client: $crate::http::Http,
+            $($field: $ty,)*
+            $($($opt: Option<$opt_ty>,)*)?
+        }
+        $(
+            #[derive(Debug, serde::Serialize)]
+            #[serde(rename_all = "camelCase")]
+            struct $body<'a> {
+                $(
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    $opt: Option<$opt_ty>,
+                )*
+            }
+        )?
+        impl<'a> $name<'a> {
+            pub fn new(client: $crate::http::Http, $($field: $ty),*) -> Self {
+                Self {
+                    client,
+                    $($field,)*
+                    $($($opt: None,)*)?
+                }
+            }
+            pub fn dry_run(&self) -> $crate::error::Result<$crate::dry_run::DryRun> {
+                self.build_request()
+                    .map(|request| $crate::dry_run::DryRun::new(&request))
+            }
+            fn build_request(&self) -> $crate::error::Result<reqwest::Request> {
+                let request = self.client.request(
+                    reqwest::Method::$method,
+                    $crate::routes::Route::$route {
+                        $($field: self.$field,)*
+                    },
+                );
+                $(
+                    let request = request.json(&$body {
+                        $($opt: self.$opt.clone(),)*
+                    });
+                )?
+
+                Ok(request.build()?)
+            }
+            $($(
+                $(#[$opt_meta])*
+                pub fn $opt(mut self, $opt: $opt_ty) -> Self {
+                    self.$opt = Some($opt);
+                    self
+                }
+            )*)?
+            request!(@send $($output)+);
+        }
+    };
+    (@send ()) => {
+        pub async fn send(self) -> $crate::error::Result<()> {
+            self.send_with_meta().await.map(|_| ())
+        }
+        pub async fn send_with_meta(self) -> $crate::error::Result<$crate::meta::ResponseMeta> {
+            let request = self.build_request()?;
+            let response = self.client.execute(request).await?;
+            let response = $crate::error::ResponseExt::check_status(response)?;
+
+            Ok($crate::meta::ResponseMeta::new(&response))
+        }
+    };
+    (@send $response:ident.$value:ident: $output:ty) => {
+        pub async fn send(self) -> $crate::error::Result<$output> {
+            self.send_with_meta().await.map(|(value, _)| value)
+        }
+        pub async fn send_with_meta(
+            self,
+        ) -> $crate::error::Result<($output, $crate::meta::ResponseMeta)> {
+            let request = self.build_request()?;
+            let response = self.client.execute(request).await?;
+            let response = $crate::error::ResponseExt::check_status(response)?;
+            let meta = $crate::meta::ResponseMeta::new(&response);
+            let response: $response = response.json().await?;
+
+            Ok((response.$value, meta))
+        }
+    };
+}
@@ -0,0 +1,174 @@
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Request};
+use serde::{Deserialize, Serialize};
+
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
+use crate::message::{ChatEmbedImage, ChatEmbedThumbnail};
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
+
+// Note: not part of the documented bot API, this is the upload route used by the Guilded client
+static MEDIA_UPLOAD: &str = "https://media.guilded.gg/media/upload?dynamicMediaTypeId=ContentMedia";
+
+/// An uploaded file, hosted on Guilded's CDN
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadedMedia {
+    url: String,
+}
+impl UploadedMedia {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    pub fn image(&self) -> Result<ChatEmbedImage> {
+        ChatEmbedImage::new(self.url.as_str())
+    }
+    pub fn thumbnail(&self) -> Result<ChatEmbedThumbnail> {
+        ChatEmbedThumbnail::new(self.url.as_str())
+    }
+}
+
+/// Upload of a file to Guilded's CDN.
+///
+/// Not part of the documented bot API: this is the route the Guilded web client uploads through,
+/// and the response shape (a JSON object with the hosted `url`) is inferred from what it returns,
+/// so it may change without notice. The upload goes to `media.guilded.gg` rather than the API, so
+/// it's sent without the bot token.
+#[derive(Debug)]
+pub struct UploadMediaRequest<'a> {
+    client: Http,
+    file_name: &'a str,
+    data: Vec<u8>,
+    mime: Option<&'a str>,
+}
+impl<'a> UploadMediaRequest<'a> {
+    pub fn new(client: Http, file_name: &'a str, data: Vec<u8>) -> Self {
+        Self {
+            client,
+            file_name,
+            data,
+            mime: None,
+        }
+    }
+    pub async fn send(self) -> Result<UploadedMedia> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub async fn send_with_meta(self) -> Result<(UploadedMedia, ResponseMeta)> {
+        let mime = self.mime.or_else(|| guess_mime(self.file_name));
+        let mut part = Part::bytes(self.data).file_name(self.file_name.to_owned());
+        if let Some(mime) = mime {
+            part = part.mime_str(mime)?;
+        }
+        // Uploads go to the media host rather than an API route, so the token stays out of them
+        let request = Client::post(self.client.assets(), MEDIA_UPLOAD)
+            .multipart(Form::new().part("file", part))
+            .build()?;
+        let response = self
+            .client
+            .assets()
+            .execute(request)
+            .await?
+            .check_status()?;
+        let meta = ResponseMeta::new(&response);
+        let media: UploadedMedia = response.json().await?;
+
+        Ok((media, meta))
+    }
+    /// Sets the content type, which is otherwise guessed from the file name's extension
+    pub fn mime(mut self, mime: &'a str) -> Self {
+        self.mime = Some(mime);
+        self
+    }
+}
+
+/// Signed version of a CDN URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UrlSignature {
+    /// The URL that was asked to be signed
+    url: String,
+    /// The signed URL, if signing succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+    /// Seconds to wait before retrying, if the URL could not be signed yet
+    #[serde(rename = "retryAfter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_after: Option<u64>,
+}
+impl UrlSignature {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    pub fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after.map(Duration::from_secs)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CreateUrlSignaturesBody<'a> {
+    urls: &'a [&'a str],
+}
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateUrlSignaturesResponse {
+    #[serde(rename = "urlSignatures")]
+    signatures: Vec<UrlSignature>,
+}
+#[derive(Debug)]
+pub struct CreateUrlSignaturesRequest<'a> {
+    client: Http,
+    urls: Vec<&'a str>,
+}
+impl<'a> CreateUrlSignaturesRequest<'a> {
+    pub fn new(client: Http, urls: &[&'a str]) -> Self {
+        Self {
+            client,
+            urls: urls.to_vec(),
+        }
+    }
+    /// Signatures are returned in the same order as the URLs were given
+    pub async fn send(self) -> Result<Vec<UrlSignature>> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        let body = CreateUrlSignaturesBody { urls: &self.urls };
+        let request = self.client.post(Route::UrlSignatures).json(&body).build()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(Vec<UrlSignature>, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+        let signatures: CreateUrlSignaturesResponse = response.json().await?;
+
+        Ok((signatures.signatures, meta))
+    }
+    pub fn add_url(mut self, url: &'a str) -> Self {
+        self.urls.push(url);
+        self
+    }
+}
+
+fn guess_mime(file_name: &str) -> Option<&'static str> {
+    let extension = Path::new(file_name).extension()?.to_str()?;
+    let mime = match extension.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "apng" => "image/apng",
+        _ => return None,
+    };
+    Some(mime)
+}
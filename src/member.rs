@@ -4,13 +4,22 @@ use std::{collections::HashSet, fmt::Display, ops::Deref};
 
 use async_stream::stream;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::{Request, Url};
 use serde::{Deserialize, Serialize};
-use tokio_stream::Stream;
+use tokio::task::JoinSet;
+use tokio_stream::{Stream, StreamExt};
 
-use crate::error::Result;
+use crate::cdn::{self, ImageSize};
+use crate::dry_run::DryRun;
+use crate::error::{Error, ResponseExt, Result};
+use crate::http::Http;
+use crate::inflight::Inflight;
+use crate::json_items::JsonItems;
+use crate::meta::ResponseMeta;
+use crate::reactions::EmoteId;
 use crate::roles::RoleId;
-use crate::API_BASE;
+use crate::routes::Route;
+use crate::streams::ApiStream;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 #[repr(transparent)]
@@ -114,13 +123,13 @@ impl FromStr for ServerId {
     }
 }
 
-#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum UserType {
-    /// The user is a bot
-    Bot,
-    /// The user is a human
-    User,
+api_enum! {
+    pub enum UserType {
+        /// The user is a bot
+        Bot = "bot",
+        /// The user is a human
+        User = "user",
+    }
 }
 
 fn default_usertype() -> UserType {
@@ -134,6 +143,7 @@ pub struct User {
     id: UserId,
     /// Type of user
     #[serde(default = "default_usertype")]
+    #[serde(rename = "type")]
     user_type: UserType,
     /// Name of user
     name: String,
@@ -144,6 +154,65 @@ pub struct User {
     /// Timestamp of when the user was created
     #[serde(rename = "createdAt")]
     created: DateTime<Utc>,
+    /// Custom status shown on the user's profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<UserStatus>,
+}
+
+impl User {
+    pub fn id(&self) -> &UserId {
+        &self.id
+    }
+    pub fn user_type(&self) -> &UserType {
+        &self.user_type
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn status(&self) -> Option<&UserStatus> {
+        self.status.as_ref()
+    }
+    /// URL of the user's avatar, if they have one
+    pub fn avatar_url(&self) -> Option<Url> {
+        self.avatar.as_deref()?.parse().ok()
+    }
+    /// URL of the `size` variant of the user's avatar, if they have one
+    pub fn avatar_url_sized(&self, size: ImageSize) -> Option<Url> {
+        self.avatar_url().map(|url| cdn::resize(&url, size))
+    }
+    /// URL of the user's profile banner, if they have one
+    pub fn banner_url(&self) -> Option<Url> {
+        self.banner.as_deref()?.parse().ok()
+    }
+    /// URL of the `size` variant of the user's profile banner, if they have one
+    pub fn banner_url_sized(&self, size: ImageSize) -> Option<Url> {
+        self.banner_url().map(|url| cdn::resize(&url, size))
+    }
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+}
+
+/// Custom status of a user, see [`User::status`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(rename = "emoteId")]
+    emote: EmoteId,
+}
+impl UserStatus {
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref()
+    }
+    pub fn emote(&self) -> EmoteId {
+        self.emote
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -159,6 +228,27 @@ pub struct ServerMember {
     /// Timestamp of when the user joined the server
     #[serde(rename = "joinedAt")]
     joined: DateTime<Utc>,
+    /// Whether the user owns the server (default: false)
+    #[serde(rename = "isOwner")]
+    #[serde(default)]
+    owner: bool,
+}
+impl ServerMember {
+    /// Roles assigned to the member
+    pub fn roles(&self) -> &HashSet<RoleId> {
+        &self.roles
+    }
+    /// Whether the member owns the server, and so bypasses every permission check
+    pub fn is_owner(&self) -> bool {
+        self.owner
+    }
+    pub fn joined(&self) -> &DateTime<Utc> {
+        &self.joined
+    }
+    #[cfg(feature = "time")]
+    pub fn joined_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.joined)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -176,6 +266,20 @@ pub struct UserSummary {
     avatar: Option<String>,
 }
 
+impl UserSummary {
+    pub fn id(&self) -> &UserId {
+        &self.id
+    }
+    /// URL of the user's avatar, if they have one
+    pub fn avatar_url(&self) -> Option<Url> {
+        self.avatar.as_deref()?.parse().ok()
+    }
+    /// URL of the `size` variant of the user's avatar, if they have one
+    pub fn avatar_url_sized(&self, size: ImageSize) -> Option<Url> {
+        self.avatar_url().map(|url| cdn::resize(&url, size))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ServerMemberSummary {
@@ -185,6 +289,11 @@ pub struct ServerMemberSummary {
     #[serde(rename = "roleIds")]
     roles: HashSet<RoleId>,
 }
+impl ServerMemberSummary {
+    pub fn user(&self) -> &UserSummary {
+        &self.user
+    }
+}
 
 #[derive(Debug, Serialize)]
 struct UpdateNicknameRequestData<'a> {
@@ -199,13 +308,13 @@ struct UpdateNicknameResponse {
 
 #[derive(Debug)]
 pub struct UpdateNicknameRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
     nickname: UpdateNicknameRequestData<'a>,
 }
 impl<'a> UpdateNicknameRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId, nickname: &'a str) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId, nickname: &'a str) -> Self {
         Self {
             client,
             server,
@@ -214,47 +323,92 @@ impl<'a> UpdateNicknameRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<String> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         // TODO: sanitize server/user
         let request = self
             .client
-            .put(format!(
-                "{API_BASE}/servers/{}/members/{}/nickname",
-                self.server, self.user
-            ))
+            .put(Route::MemberNickname {
+                server: self.server,
+                user: self.user,
+            })
             .json(&self.nickname)
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(String, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let nickname: UpdateNicknameResponse = response.json().await?;
 
-        Ok(nickname.nickname)
+        Ok((nickname.nickname, meta))
+    }
+}
+
+request! {
+    pub struct DeleteNicknameRequest<'a> {
+        server: &'a ServerId,
+        user: &'a UserId,
     }
+    DELETE MemberNickname => ()
 }
 
+/// Sets or clears a nickname, using [`UpdateNicknameRequest`] or [`DeleteNicknameRequest`]
 #[derive(Debug)]
-pub struct DeleteNicknameRequest<'a> {
-    client: Client,
+pub struct SetNicknameRequest<'a> {
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
-}
-impl<'a> DeleteNicknameRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId) -> Self {
+    nickname: Option<&'a str>,
+}
+impl<'a> SetNicknameRequest<'a> {
+    pub fn new(
+        client: Http,
+        server: &'a ServerId,
+        user: &'a UserId,
+        nickname: Option<&'a str>,
+    ) -> Self {
         Self {
             client,
             server,
             user,
+            nickname,
         }
     }
-    pub async fn send(self) -> Result<()> {
-        let request = self
-            .client
-            .delete(format!(
-                "{API_BASE}/servers/{}/members/{}/nickname",
-                self.server, self.user
-            ))
-            .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
-
-        Ok(())
+    /// Returns the nickname the member now has
+    pub async fn send(self) -> Result<Option<String>> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        match self.nickname {
+            Some(nickname) => {
+                UpdateNicknameRequest::new(self.client.clone(), self.server, self.user, nickname)
+                    .dry_run()
+            }
+            None => {
+                DeleteNicknameRequest::new(self.client.clone(), self.server, self.user).dry_run()
+            }
+        }
+    }
+    pub async fn send_with_meta(self) -> Result<(Option<String>, ResponseMeta)> {
+        match self.nickname {
+            Some(nickname) => {
+                UpdateNicknameRequest::new(self.client, self.server, self.user, nickname)
+                    .send_with_meta()
+                    .await
+                    .map(|(nickname, meta)| (Some(nickname), meta))
+            }
+            None => DeleteNicknameRequest::new(self.client, self.server, self.user)
+                .send_with_meta()
+                .await
+                .map(|meta| (None, meta)),
+        }
     }
 }
 
@@ -265,92 +419,214 @@ struct GetMemberResponse {
 }
 #[derive(Debug)]
 pub struct GetMemberRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
+    inflight: Option<Inflight>,
 }
 impl<'a> GetMemberRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId) -> Self {
         Self {
             client,
             server,
             user,
+            inflight: None,
         }
     }
     pub async fn send(self) -> Result<ServerMember> {
-        let request = self
-            .client
-            .get(format!(
-                "{API_BASE}/servers/{}/members/{}",
-                self.server, self.user
-            ))
-            .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
-        let member: GetMemberResponse = response.json().await?;
-        Ok(member.member)
+        self.send_with_meta().await.map(|(value, _)| value)
     }
-}
-
-#[derive(Debug)]
-pub struct KickMemberRequest<'a> {
-    client: Client,
-    server: &'a ServerId,
-    user: &'a UserId,
-}
-impl<'a> KickMemberRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId) -> Self {
-        KickMemberRequest {
-            client,
-            server,
-            user,
-        }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
     }
-    pub async fn send(self) -> Result<()> {
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .delete(format!(
-                "{API_BASE}/servers/{}/members/{}",
-                self.server, self.user
-            ))
+            .get(Route::Member {
+                server: self.server,
+                user: self.user,
+            })
             .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
 
-        Ok(())
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ServerMember, ResponseMeta)> {
+        let request = self.build_request()?;
+        let (body, meta) = match &self.inflight {
+            Some(inflight) => inflight
+                .execute(&self.client, request)
+                .await
+                .map_err(|e| e.not_found("member", self.user))?,
+            None => {
+                let response = self
+                    .client
+                    .execute(request)
+                    .await?
+                    .check_status()
+                    .map_err(|e| e.not_found("member", self.user))?;
+                let meta = ResponseMeta::new(&response);
+                (response.bytes().await?.to_vec(), meta)
+            }
+        };
+        let member: GetMemberResponse = serde_json::from_slice(&body)?;
+        Ok((member.member, meta))
+    }
+    pub(crate) fn inflight(mut self, inflight: Option<Inflight>) -> Self {
+        self.inflight = inflight;
+        self
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-struct GetMembersResponse {
-    members: Vec<ServerMemberSummary>,
+request! {
+    pub struct KickMemberRequest<'a> {
+        server: &'a ServerId,
+        user: &'a UserId,
+    }
+    DELETE Member => ()
 }
+
 #[derive(Debug)]
 struct MemberStream;
 impl MemberStream {
-    fn iter(gmr: GetMembersRequest) -> impl Stream<Item = Result<ServerMemberSummary>> + '_ {
+    fn iter(
+        mut gmr: GetMembersRequest<'_>,
+    ) -> impl Stream<Item = Result<ServerMemberSummary>> + '_ {
         stream! {
             let request = gmr
                 .client
-                .get(format!("{API_BASE}/servers/{}/members", gmr.server))
+                .get(Route::Members { server: gmr.server })
                 .build()?;
-            let response = gmr.client.execute(request).await?.error_for_status()?;
-            let members: GetMembersResponse = response.json().await?;
-            for member in members.members {
-                yield Ok(member);
+            let mut response = gmr.client.execute(request).await?.check_status()?;
+            // Members are parsed as the body arrives rather than after it's all been read
+            let mut items = JsonItems::new();
+            let mut count = 0;
+            let mut resuming = gmr.resume_after.is_some();
+            while let Some(chunk) = response.chunk().await? {
+                for item in items.push(&chunk) {
+                    let member: ServerMemberSummary = serde_json::from_slice(&item)?;
+                    count += 1;
+                    if let Some(progress) = &mut gmr.progress {
+                        progress(count);
+                    }
+                    if resuming {
+                        resuming = Some(member.user.id()) != gmr.resume_after;
+                        continue;
+                    }
+                    yield Ok(member);
+                }
+            }
+            // The checkpoint left the server, so there's no telling where to pick back up
+            if let (true, Some(user)) = (resuming, gmr.resume_after) {
+                yield Err(Error::NotFound {
+                    resource: "member",
+                    id: user.to_string(),
+                    context: None,
+                });
             }
         }
     }
 }
-#[derive(Debug)]
+/// Members of a server, see [`GetMembersRequest::send`]
+pub type MembersStream<'a> = ApiStream<'a, ServerMemberSummary>;
+
 pub struct GetMembersRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
+    resume_after: Option<&'a UserId>,
+    progress: Option<Box<dyn FnMut(usize) + Send + 'a>>,
 }
 impl<'a> GetMembersRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId) -> Self {
-        Self { client, server }
+    pub fn new(client: Http, server: &'a ServerId) -> Self {
+        Self {
+            client,
+            server,
+            resume_after: None,
+            progress: None,
+        }
+    }
+    /// Yields members as they are read from the response, so large servers aren't held in memory
+    pub fn send(self) -> MembersStream<'a> {
+        ApiStream::new(MemberStream::iter(self))
+    }
+    /// Reads all the members into a `Vec`, for when they aren't needed one at a time
+    pub async fn collect_all(self) -> Result<Vec<ServerMemberSummary>> {
+        self.send().collect_all().await
+    }
+    /// Skips members up to and including `user`, to pick a listing back up after it was interrupted.
+    ///
+    /// Relies on the API listing members in the same order each time. If `user` isn't in the
+    /// listing anymore, nothing is yielded but a final [`Error::NotFound`].
+    pub fn resume_after(mut self, user: &'a UserId) -> Self {
+        self.resume_after = Some(user);
+        self
+    }
+    /// Called with the number of members read so far, including skipped ones, as each one is read
+    pub fn progress(mut self, progress: impl FnMut(usize) + Send + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
+impl std::fmt::Debug for GetMembersRequest<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GetMembersRequest")
+            .field("client", &self.client)
+            .field("server", &self.server)
+            .field("resume_after", &self.resume_after)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Full records of a server's members, see [`DetailedMembersRequest::send`]
+pub type DetailedMembersStream<'a> = ApiStream<'a, ServerMember>;
+
+/// Fetches the full record of every member of a server, a few at a time
+#[derive(Debug)]
+pub struct DetailedMembersRequest<'a> {
+    client: Http,
+    server: &'a ServerId,
+    concurrency: usize,
+}
+impl<'a> DetailedMembersRequest<'a> {
+    pub fn new(client: Http, server: &'a ServerId) -> Self {
+        Self {
+            client,
+            server,
+            concurrency: 4,
+        }
+    }
+    /// Yields each member, in the order the lookups complete.
+    ///
+    /// Rate limited lookups wait and are retried like bans are, see
+    /// [`GuildedClientBuilder::rate_limit_retries`](crate::GuildedClientBuilder::rate_limit_retries).
+    pub fn send(self) -> DetailedMembersStream<'a> {
+        ApiStream::new(stream! {
+            let members = GetMembersRequest::new(self.client.clone(), self.server).send();
+            tokio::pin!(members);
+            let mut tasks = JoinSet::new();
+            let mut members_done = false;
+            loop {
+                while !members_done && tasks.len() < self.concurrency.max(1) {
+                    let Some(member) = members.next().await else {
+                        members_done = true;
+                        break;
+                    };
+                    let user = member?.user.id().clone();
+                    let client = self.client.clone();
+                    let server = self.server.clone();
+                    tasks.spawn(async move { GetMemberRequest::new(client, &server, &user).send().await });
+                }
+                let Some(joined) = tasks.join_next().await else {
+                    break;
+                };
+                // Tasks are never aborted and lookups don't panic
+                yield joined.expect("member task failed");
+            }
+        })
     }
-    pub fn send(self) -> impl Stream<Item = Result<ServerMemberSummary>> + 'a {
-        MemberStream::iter(self)
+    /// How many members are fetched at the same time (default: 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
     }
 }
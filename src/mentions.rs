@@ -0,0 +1,104 @@
+//! Mention markup in message content: `<@user>`, `<@role>` (numeric) and `<#channel>`
+
+use std::fmt::Display;
+use std::ops::Range;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use crate::channel::ChannelId;
+use crate::member::UserId;
+use crate::roles::RoleId;
+
+/// Length of every user ID, e.g. `Ann6LewA`
+const USER_ID_LENGTH: usize = 8;
+
+/// Something mentioned in a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mention {
+    User(UserId),
+    Channel(ChannelId),
+    Role(RoleId),
+}
+impl From<UserId> for Mention {
+    fn from(user: UserId) -> Self {
+        Self::User(user)
+    }
+}
+impl From<ChannelId> for Mention {
+    fn from(channel: ChannelId) -> Self {
+        Self::Channel(channel)
+    }
+}
+impl From<RoleId> for Mention {
+    fn from(role: RoleId) -> Self {
+        Self::Role(role)
+    }
+}
+impl Display for Mention {
+    /// Markup of the mention, to put in a message's content
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::User(user) => write!(f, "<@{user}>"),
+            Self::Channel(channel) => write!(f, "<#{channel}>"),
+            Self::Role(role) => write!(f, "<@{role}>"),
+        }
+    }
+}
+impl FromStr for Mention {
+    type Err = ();
+
+    /// Parses a string that is exactly one mention, e.g. the argument of `!ban <@user>`
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        let inner = s.strip_prefix('<').and_then(|s| s.strip_suffix('>'));
+        let inner = inner.ok_or(())?;
+        if let Some(channel) = inner.strip_prefix('#') {
+            return channel.parse().map(Self::Channel).map_err(|_| ());
+        }
+        let id = inner.strip_prefix('@').ok_or(())?;
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(());
+        }
+        // User IDs are always 8 characters, usually mixing letters and digits, while role IDs
+        // are shorter numbers, so an ID of 8 digits is still a user
+        if id.len() == USER_ID_LENGTH {
+            return id.parse().map(Self::User);
+        }
+        match id.parse() {
+            Ok(role) => Ok(Self::Role(RoleId::new(role))),
+            Err(_) => id.parse().map(Self::User),
+        }
+    }
+}
+
+/// Mentions in `content`, with the byte range of their markup, in order
+pub fn parse(content: &str) -> impl Iterator<Item = (Range<usize>, Mention)> + '_ {
+    content.match_indices('<').filter_map(move |(start, _)| {
+        let end = start + content[start..].find('>')? + 1;
+        let mention = content[start..end].parse().ok()?;
+        Some((start..end, mention))
+    })
+}
+
+/// Users mentioned in `content`, in order
+pub fn users(content: &str) -> impl Iterator<Item = UserId> + '_ {
+    parse(content).filter_map(|(_, mention)| match mention {
+        Mention::User(user) => Some(user),
+        _ => None,
+    })
+}
+
+/// Channels mentioned in `content`, in order
+pub fn channels(content: &str) -> impl Iterator<Item = ChannelId> + '_ {
+    parse(content).filter_map(|(_, mention)| match mention {
+        Mention::Channel(channel) => Some(channel),
+        _ => None,
+    })
+}
+
+/// Roles mentioned in `content`, in order
+pub fn roles(content: &str) -> impl Iterator<Item = RoleId> + '_ {
+    parse(content).filter_map(|(_, mention)| match mention {
+        Mention::Role(role) => Some(role),
+        _ => None,
+    })
+}
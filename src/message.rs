@@ -1,19 +1,32 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fmt::Display;
+use std::path::{Path, PathBuf};
 use std::result::Result as StdResult;
 use std::str::FromStr;
 use std::{mem, ops::Deref};
 
 use crate::channel::ChannelId;
+use crate::dry_run::DryRun;
+use crate::media::UploadMediaRequest;
 use crate::member::UserId;
-use crate::API_BASE;
+use crate::meta::ResponseMeta;
+use crate::queue::Coalesced;
+use crate::reactions::EmoteId;
+use crate::routes::Route;
+use crate::streams::{ApiStream, Page};
+use crate::GuildedClient;
 use async_stream::stream;
-use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
-use reqwest::{Client, IntoUrl, Url};
+use chrono::{DateTime, Duration, SecondsFormat, TimeZone, Utc};
+use reqwest::{IntoUrl, Request};
 use serde::{Deserialize, Serialize};
-use tokio_stream::Stream;
+use thiserror::Error;
+use tokio::fs;
+use tokio_stream::{Stream, StreamExt};
 use uuid::Uuid;
 
-use crate::error::Result;
+use crate::error::{Constraint, Error, ResponseExt, Result};
+use crate::http::Http;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 #[repr(transparent)]
@@ -125,14 +138,14 @@ impl FromStr for WebhookId {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum MessageType {
-    Default,
-    System,
+api_enum! {
+    pub enum MessageType {
+        Default = "default",
+        System = "system",
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
 pub struct ChatMessage {
@@ -152,6 +165,10 @@ pub struct ChatMessage {
     #[serde(default)]
     #[serde(rename = "isPrivate")]
     private: bool,
+    #[serde(default)]
+    #[serde(rename = "hiddenLinkPreviewUrls")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hidden_link_previews: Vec<String>,
     created_at: DateTime<Utc>,
     created_by: Option<UserId>,
     #[serde(rename = "createdByWebhookId")]
@@ -163,8 +180,8 @@ impl ChatMessage {
     pub fn id(&self) -> MessageId {
         self.id
     }
-    pub fn message_type(&self) -> MessageType {
-        self.message_type
+    pub fn message_type(&self) -> &MessageType {
+        &self.message_type
     }
     pub fn server(&self) -> Option<&str> {
         self.server.as_ref().map(|v| v as _)
@@ -184,6 +201,10 @@ impl ChatMessage {
     pub fn private(&self) -> bool {
         self.private
     }
+    /// Links in the content that are shown without a preview
+    pub fn hidden_link_previews(&self) -> &[String] {
+        &self.hidden_link_previews
+    }
     pub fn created_at(&self) -> &DateTime<Utc> {
         &self.created_at
     }
@@ -196,9 +217,83 @@ impl ChatMessage {
     pub fn updated(&self) -> Option<&DateTime<Utc>> {
         self.updated.as_ref()
     }
+    #[cfg(feature = "time")]
+    pub fn created_at_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created_at)
+    }
+    #[cfg(feature = "time")]
+    pub fn updated_time(&self) -> Option<time::OffsetDateTime> {
+        self.updated
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
+    /// Fetches the messages this one replies to, in the order of [`ChatMessage::replies`].
+    ///
+    /// Replied to messages that were deleted since are left out.
+    pub async fn fetch_replies(&self, client: &GuildedClient) -> Result<Vec<ChatMessage>> {
+        let channel = self.channel_or_err()?;
+        let mut replies = Vec::with_capacity(self.replies.len());
+        for reply in &self.replies {
+            match client.get_message(&channel, reply).send().await {
+                Ok(message) => replies.push(message),
+                Err(Error::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(replies)
+    }
+    /// Message the conversation this one is part of started with, found by following the first
+    /// message each one replies to.
+    ///
+    /// Stops early at a deleted message, a reply cycle, or after [`MAX_REPLY_DEPTH`] messages,
+    /// returning the oldest message reached. A message that isn't a reply is its own root.
+    pub async fn thread_root(&self, client: &GuildedClient) -> Result<ChatMessage> {
+        let channel = self.channel_or_err()?;
+        let mut seen = HashSet::from([self.id]);
+        let mut root = self.clone();
+        while seen.len() <= MAX_REPLY_DEPTH {
+            let Some(parent) = root.replies.first().copied() else {
+                break;
+            };
+            if !seen.insert(parent) {
+                break;
+            }
+            match client.get_message(&channel, &parent).send().await {
+                Ok(message) => root = message,
+                Err(Error::NotFound { .. }) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(root)
+    }
+    /// Reacts with each emote in turn, e.g. the options of a poll, so they show up in order.
+    ///
+    /// Rate limited reactions wait and are retried, see
+    /// [`GuildedClientBuilder::rate_limit_retries`](crate::GuildedClientBuilder::rate_limit_retries).
+    /// Stops at the first reaction that fails, leaving the ones added before it.
+    pub async fn add_reactions(&self, client: &GuildedClient, emotes: &[EmoteId]) -> Result<()> {
+        let channel = self.channel_or_err()?;
+        for emote in emotes {
+            client
+                .add_reaction(&channel, &self.id, emote)
+                .send()
+                .await?;
+        }
+        Ok(())
+    }
+    fn channel_or_err(&self) -> Result<ChannelId> {
+        self.channel.ok_or_else(|| Error::Validation {
+            field: "channel",
+            constraint: Constraint::Required,
+            value: String::new(),
+        })
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Most messages [`ChatMessage::thread_root`] walks back through
+pub const MAX_REPLY_DEPTH: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChatEmbedFooter {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -208,18 +303,18 @@ pub struct ChatEmbedFooter {
 #[derive(Debug)]
 pub struct ChatEmbedFooterBuilder(ChatEmbedFooter);
 impl ChatEmbedFooter {
-    pub fn new(text: &str) -> Self {
+    pub fn new(text: impl Into<String>) -> Self {
         Self {
             icon_url: None,
-            text: text.to_owned(),
+            text: text.into(),
         }
     }
-    pub fn builder(text: &str) -> ChatEmbedFooterBuilder {
+    pub fn builder(text: impl Into<String>) -> ChatEmbedFooterBuilder {
         ChatEmbedFooterBuilder::new(text)
     }
 }
 impl ChatEmbedFooterBuilder {
-    pub fn new(text: &str) -> Self {
+    pub fn new(text: impl Into<String>) -> Self {
         Self(ChatEmbedFooter::new(text))
     }
     pub fn build(self) -> ChatEmbedFooter {
@@ -231,7 +326,7 @@ impl ChatEmbedFooterBuilder {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChatEmbedThumbnail {
     url: String,
@@ -244,7 +339,7 @@ impl ChatEmbedThumbnail {
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChatEmbedImage {
     url: String,
@@ -257,7 +352,7 @@ impl ChatEmbedImage {
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChatEmbedAuthor {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -284,8 +379,8 @@ impl ChatEmbedAuthorBuilder {
     pub fn build(self) -> ChatEmbedAuthor {
         self.0
     }
-    pub fn name(mut self, name: &str) -> Self {
-        self.0.name = Some(name.to_owned());
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.0.name = Some(name.into());
         self
     }
     pub fn url(mut self, url: impl IntoUrl) -> Result<Self> {
@@ -300,7 +395,7 @@ impl ChatEmbedAuthorBuilder {
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChatEmbedField {
     name: String,
@@ -311,19 +406,19 @@ pub struct ChatEmbedField {
 #[derive(Debug, Default)]
 pub struct ChatEmbedFieldBuilder(ChatEmbedField);
 impl ChatEmbedField {
-    pub fn new(name: &str, value: &str) -> Self {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
         Self {
-            name: name.to_owned(),
-            value: value.to_owned(),
+            name: name.into(),
+            value: value.into(),
             inline: false,
         }
     }
-    pub fn builder(name: &str, value: &str) -> ChatEmbedFieldBuilder {
+    pub fn builder(name: impl Into<String>, value: impl Into<String>) -> ChatEmbedFieldBuilder {
         ChatEmbedFieldBuilder::new(name, value)
     }
 }
 impl ChatEmbedFieldBuilder {
-    pub fn new(name: &str, value: &str) -> Self {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
         Self(ChatEmbedField::new(name, value))
     }
     pub fn build(self) -> ChatEmbedField {
@@ -335,7 +430,7 @@ impl ChatEmbedFieldBuilder {
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
 pub struct ChatEmbed {
@@ -365,6 +460,15 @@ impl ChatEmbed {
     pub fn builder() -> ChatEmbedBuilder {
         ChatEmbedBuilder::new()
     }
+    pub fn timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.timestamp.as_ref()
+    }
+    #[cfg(feature = "time")]
+    pub fn timestamp_time(&self) -> Option<time::OffsetDateTime> {
+        self.timestamp
+            .as_ref()
+            .map(crate::timestamp::to_offset_date_time)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -376,12 +480,12 @@ impl ChatEmbedBuilder {
     pub fn build(self) -> ChatEmbed {
         self.0
     }
-    pub fn title(mut self, title: &str) -> Self {
-        self.0.title = Some(title.to_owned());
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.0.title = Some(title.into());
         self
     }
-    pub fn description(mut self, description: &str) -> Self {
-        self.0.description = Some(description.to_owned());
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.0.description = Some(description.into());
         self
     }
     pub fn url(mut self, url: impl IntoUrl) -> Result<Self> {
@@ -421,15 +525,33 @@ impl ChatEmbedBuilder {
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-struct CreateMessageResponse {
-    message: ChatMessage,
+pub(crate) struct CreateMessageResponse {
+    pub(crate) message: ChatMessage,
+}
+
+/// Maximum number of characters in a message's content
+pub const MAX_CONTENT_LENGTH: usize = 4000;
+
+/// Why a message couldn't be sent, see [`Error::Message`](crate::error::Error::Message)
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MessageError {
+    /// Content has more than [`MAX_CONTENT_LENGTH`] characters; see [`CreateMessageRequest::split_long`]
+    #[error("message content is {length} characters, the limit is {MAX_CONTENT_LENGTH}")]
+    ContentTooLong { length: usize },
+    /// An image attached by path couldn't be read
+    #[error("failed to read attachment {}: {source}", path.display())]
+    Attachment {
+        path: PathBuf,
+        source: std::io::Error,
+    },
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateMessageRequest<'a> {
     #[serde(skip)]
-    client: Client,
+    client: Http,
     #[serde(skip)]
     channel_id: &'a ChannelId,
     #[serde(rename = "isPrivate")]
@@ -441,36 +563,218 @@ pub struct CreateMessageRequest<'a> {
     #[serde(rename = "replyMessageIds")]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     replies: Vec<&'a MessageId>,
-    content: &'a str,
+    content: Cow<'a, str>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     embeds: Vec<ChatEmbed>,
+    #[serde(rename = "hiddenLinkPreviewUrls")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hidden_link_previews: Vec<String>,
+    #[serde(skip)]
+    attachments: Vec<ImageAttachment<'a>>,
+    #[serde(skip)]
+    split: bool,
 }
 impl<'a> CreateMessageRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, content: &'a str) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, content: impl Into<Cow<'a, str>>) -> Self {
         Self {
             client,
             channel_id: channel,
             private: None,
             silent: None,
             replies: Vec::new(),
-            content,
+            content: content.into(),
             embeds: Vec::new(),
+            hidden_link_previews: Vec::new(),
+            attachments: Vec::new(),
+            split: false,
         }
     }
     pub async fn send(self) -> Result<ChatMessage> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    /// Builds the request without sending it, to preview what would be sent.
+    ///
+    /// Runs the same checks as [`send`](Self::send), including that attached files can be read.
+    /// Attached images are only uploaded on send, so their embeds are missing from the body.
+    /// Content that [`split_long`](Self::split_long) splits shows as the first message it's sent as.
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.validate()?;
+        if self.split {
+            let parts = split_content(&self.content, MAX_CONTENT_LENGTH);
+            if parts.len() > 1 {
+                return self
+                    .part(parts[0], true)
+                    .build_request()
+                    .map(|request| DryRun::new(&request));
+            }
+        }
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    /// Checks made before anything is sent or uploaded
+    pub(crate) fn validate(&self) -> Result<()> {
+        self.check_length()?;
+        for attachment in &self.attachments {
+            if let ImageAttachment::Path(path) = attachment {
+                std::fs::metadata(path).map_err(|source| MessageError::Attachment {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+            }
+        }
+        Ok(())
+    }
+    fn check_length(&self) -> Result<()> {
+        let length = self.content.chars().count();
+        if !self.split && length > MAX_CONTENT_LENGTH {
+            return Err(MessageError::ContentTooLong { length }.into());
+        }
+        Ok(())
+    }
+    fn build_request(&self) -> Result<Request> {
+        self.check_length()?;
         let request = self
             .client
-            .post(format!("{API_BASE}/channels/{}/messages", self.channel_id))
-            .json(&self)
+            .post(Route::ChannelMessages {
+                channel: self.channel_id,
+            })
+            .json(self)
             .build()?;
-        let response = self.client.execute(request).await?;
-        if let Err(e) = response.error_for_status_ref() {
-            println!("Error: {e:?}");
-            println!("{}", response.text().await?);
-            return Err(e.into());
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(mut self) -> Result<(ChatMessage, ResponseMeta)> {
+        self.validate()?;
+        // Held across uploads and all parts of a split message, so nothing sent later overtakes it
+        let _turn = self.client.send_turn(self.channel_id).await;
+        for attachment in mem::take(&mut self.attachments) {
+            let (file_name, data) = match attachment {
+                ImageAttachment::Bytes { file_name, data } => (file_name.to_owned(), data),
+                ImageAttachment::Path(path) => {
+                    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                    let data = fs::read(path)
+                        .await
+                        .map_err(|source| MessageError::Attachment {
+                            path: path.to_path_buf(),
+                            source,
+                        })?;
+                    (file_name.into_owned(), data)
+                }
+            };
+            let media = UploadMediaRequest::new(self.client.clone(), &file_name, data)
+                .send()
+                .await?;
+            let embed = ChatEmbed::builder().image(media.image()?).build();
+            self.embeds.push(embed);
+        }
+        if let Some(throttle) = self.client.throttle().filter(|_| self.is_plain()) {
+            match throttle.coalesce(self.channel_id, &self.content).await {
+                Coalesced::Send => return self.post().await,
+                Coalesced::Lead(leader) => {
+                    let merged = CreateMessageRequest {
+                        content: leader.content().into(),
+                        ..self
+                    };
+                    let result = merged.post().await;
+                    leader.finish(result.as_ref().ok());
+                    return result;
+                }
+                Coalesced::Merged(mut done) => {
+                    if let Ok(sent) = done.wait_for(Option::is_some).await {
+                        if let Some(sent) = sent.as_ref() {
+                            return Ok(sent.as_ref().clone());
+                        }
+                    }
+                    // The merged message wasn't sent, so send this one on its own
+                    return self.throttled_post().await;
+                }
+            }
+        }
+        if self.split {
+            let content = mem::take(&mut self.content);
+            let mut parts = split_content(&content, MAX_CONTENT_LENGTH);
+            if let Some(last) = parts.pop() {
+                for (i, part) in parts.iter().enumerate() {
+                    self.part(part, i == 0).throttled_post().await?;
+                }
+                if !parts.is_empty() {
+                    self.replies.clear();
+                }
+                self.content = last.to_owned().into();
+            }
+        }
+        self.throttled_post().await
+    }
+    /// One of the messages split content is sent as, other than the last: replies go on the first
+    /// part and embeds on the last
+    fn part<'b>(&'b self, content: &'b str, first: bool) -> CreateMessageRequest<'b> {
+        CreateMessageRequest {
+            client: self.client.clone(),
+            channel_id: self.channel_id,
+            private: self.private,
+            silent: self.silent,
+            replies: if first {
+                self.replies.clone()
+            } else {
+                Vec::new()
+            },
+            hidden_link_previews: self
+                .hidden_link_previews
+                .iter()
+                .filter(|url| content.contains(url.as_str()))
+                .cloned()
+                .collect(),
+            content: content.into(),
+            embeds: Vec::new(),
+            attachments: Vec::new(),
+            split: false,
+        }
+    }
+    /// Messages split content is sent as, in order, or `None` if it goes as a single message
+    pub(crate) fn split_parts(&self) -> Option<Vec<CreateMessageRequest<'_>>> {
+        if !self.split {
+            return None;
+        }
+        let parts = split_content(&self.content, MAX_CONTENT_LENGTH);
+        if parts.len() < 2 {
+            return None;
         }
+        let last = parts.len() - 1;
+        let parts = parts.iter().enumerate().map(|(i, part)| {
+            let mut request = self.part(part, i == 0);
+            if i == last {
+                request.embeds = self.embeds.clone();
+            }
+            request
+        });
+        Some(parts.collect())
+    }
+    async fn throttled_post(&self) -> Result<(ChatMessage, ResponseMeta)> {
+        if let Some(throttle) = self.client.throttle() {
+            throttle.acquire(self.channel_id).await;
+        }
+        self.post().await
+    }
+    /// Whether the message is only text, and so can be merged with others by the send throttle
+    fn is_plain(&self) -> bool {
+        self.private.is_none()
+            && self.silent.is_none()
+            && self.replies.is_empty()
+            && self.embeds.is_empty()
+            && !self.split
+            && self.content.chars().count() <= MAX_CONTENT_LENGTH
+    }
+    async fn post(&self) -> Result<(ChatMessage, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let message: CreateMessageResponse = response.json().await?;
-        Ok(message.message)
+        Ok((message.message, meta))
+    }
+    pub(crate) fn channel(&self) -> &ChannelId {
+        self.channel_id
+    }
+    pub(crate) fn has_attachments(&self) -> bool {
+        !self.attachments.is_empty()
     }
     pub fn private(mut self, private: bool) -> Self {
         self.private = Some(private);
@@ -488,16 +792,89 @@ impl<'a> CreateMessageRequest<'a> {
         self.embeds.push(embed);
         self
     }
+    /// Shows `url`, a link in the content, without a preview.
+    ///
+    /// The URL is sent as given, so it has to be written the same way as in the content.
+    pub fn hide_link_preview(mut self, url: impl Into<String>) -> Result<Self> {
+        let url = url.into();
+        check_url(url.as_str())?;
+        self.hidden_link_previews.push(url);
+        Ok(self)
+    }
+    /// Uploads an image when the message is sent and adds an embed showing it
+    pub fn attach_image(mut self, image: impl Into<ImageAttachment<'a>>) -> Self {
+        self.attachments.push(image.into());
+        self
+    }
+    /// Sends content over [`MAX_CONTENT_LENGTH`] as several messages, split at paragraph, line or word boundaries.
+    ///
+    /// The returned message is the last part, which also carries the embeds.
+    pub fn split_long(mut self) -> Self {
+        self.split = true;
+        self
+    }
+}
+
+/// Splits `content` into parts of at most `max` characters, preferring to break between paragraphs,
+/// then lines, then words
+fn split_content(content: &str, max: usize) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = content;
+    while rest.chars().count() > max {
+        let end = rest.char_indices().nth(max).map_or(rest.len(), |(i, _)| i);
+        let window = &rest[..end];
+        let cut = ["\n\n", "\n", " "]
+            .iter()
+            .find_map(|separator| {
+                window
+                    .rfind(separator)
+                    .filter(|&i| i > 0)
+                    .map(|i| i + separator.len())
+            })
+            .unwrap_or(end);
+        let (part, tail) = rest.split_at(cut);
+        if !part.trim().is_empty() {
+            parts.push(part.trim_end());
+        }
+        rest = tail.trim_start();
+    }
+    parts.push(rest);
+    parts
+}
+
+/// Image to upload alongside a message, see [`CreateMessageRequest::attach_image`]
+#[derive(Debug)]
+pub enum ImageAttachment<'a> {
+    Bytes { file_name: &'a str, data: Vec<u8> },
+    Path(&'a Path),
+}
+impl<'a> From<&'a Path> for ImageAttachment<'a> {
+    fn from(path: &'a Path) -> Self {
+        Self::Path(path)
+    }
+}
+impl<'a> From<&'a PathBuf> for ImageAttachment<'a> {
+    fn from(path: &'a PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+impl<'a> From<(&'a str, Vec<u8>)> for ImageAttachment<'a> {
+    fn from((file_name, data): (&'a str, Vec<u8>)) -> Self {
+        Self::Bytes { file_name, data }
+    }
 }
 
+/// History of a channel, newest first, see [`GetChannelMessagesRequest::send`]
+pub type MessagesStream<'a> = ApiStream<'a, ChatMessage>;
+
 #[derive(Debug)]
 pub struct GetChannelMessagesRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
-    before: Option<String>,
-    after: Option<String>,
+    before: Option<DateTime<Utc>>,
+    after: Option<DateTime<Utc>>,
     limit: Option<u32>,
-    private: Option<bool>,
+    include_private: Option<bool>,
 }
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -505,116 +882,207 @@ struct GetChannelMessagesResponse {
     messages: Vec<ChatMessage>,
 }
 impl<'a> GetChannelMessagesRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId) -> Self {
         Self {
             client,
             channel,
             before: None,
             after: None,
             limit: None,
-            private: None,
+            include_private: None,
         }
     }
-    pub fn send(self) -> impl Stream<Item = Result<ChatMessage>> + 'a {
-        ChannelMessageStream::iter(self)
+    pub fn send(self) -> MessagesStream<'a> {
+        ApiStream::new(ChannelMessageStream::iter(self))
+    }
+    /// Reads all the messages into a `Vec`, for when they aren't needed one at a time
+    pub async fn collect_all(self) -> Result<Vec<ChatMessage>> {
+        self.send().collect_all().await
+    }
+    /// Fetches a single page of messages, newest first, for callers that paginate themselves.
+    ///
+    /// Passing [`Page::next`] to [`before`](Self::before) fetches the page after it.
+    pub async fn send_page(self) -> Result<Page<ChatMessage>> {
+        let after = self.after;
+        let mut messages = self.send_part().await?;
+        let end = after.and_then(|after| {
+            messages
+                .iter()
+                .position(|message| message.created_at <= after)
+        });
+        let next = match end {
+            Some(end) => {
+                messages.truncate(end);
+                None
+            }
+            None => messages.last().map(|message| message.created_at),
+        };
+        Ok(Page::new(messages, next))
     }
-    async fn send_part(self) -> Result<Vec<ChatMessage>> {
-        let mut url: Url = format!("{API_BASE}/channels/{}/messages", self.channel)
-            .parse()
-            .unwrap();
+    /// Builds the request for the first page without sending it
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        let mut query = Vec::new();
         if let Some(before) = self.before {
-            url.set_query(Some(&format!("before={before}&")));
+            query.push((
+                "before",
+                before.to_rfc3339_opts(SecondsFormat::Millis, true),
+            ));
         }
         if let Some(after) = self.after {
-            url.set_query(Some(&format!(
-                "{}after={after}&",
-                url.query().unwrap_or_default()
-            )));
+            query.push(("after", after.to_rfc3339_opts(SecondsFormat::Millis, true)));
         }
         if let Some(limit) = self.limit {
-            url.set_query(Some(&format!(
-                "{}limit={limit}&",
-                url.query().unwrap_or_default()
-            )));
+            query.push(("limit", limit.to_string()));
         }
-        if let Some(private) = self.private {
-            url.set_query(Some(&format!(
-                "{}private={private}&",
-                url.query().unwrap_or_default()
-            )));
+        if let Some(include_private) = self.include_private {
+            query.push(("includePrivate", include_private.to_string()));
         }
-        let request = self.client.get(url).build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+        let request = self
+            .client
+            .get(Route::ChannelMessages {
+                channel: self.channel,
+            })
+            .query(&query)
+            .build()?;
+
+        Ok(request)
+    }
+    async fn send_part(self) -> Result<Vec<ChatMessage>> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
         let messages: GetChannelMessagesResponse = response.json().await?;
         Ok(messages.messages)
     }
     pub fn before<T: TimeZone>(mut self, before: DateTime<T>) -> Self {
-        let before = before.with_timezone(&Utc);
-        self.before = Some(before.to_rfc3339_opts(SecondsFormat::Millis, true));
+        self.before = Some(before.with_timezone(&Utc));
         self
     }
+    /// Only returns messages created after this time; the stream ends once it is reached
     pub fn after<T: TimeZone>(mut self, after: DateTime<T>) -> Self {
-        let after = after.with_timezone(&Utc);
-        self.after = Some(after.to_rfc3339_opts(SecondsFormat::Millis, true));
+        self.after = Some(after.with_timezone(&Utc));
         self
     }
+    /// Only returns messages created from `start` to `end`, both included.
+    ///
+    /// The API's bounds leave the given times out and only go down to milliseconds, so they're
+    /// widened by a millisecond on each side.
+    pub fn between<T: TimeZone>(self, start: DateTime<T>, end: DateTime<T>) -> Self {
+        let millisecond = Duration::milliseconds(1);
+        self.after(start - millisecond).before(end + millisecond)
+    }
     //pub fn limit(mut self, limit: u32) -> Self {
     //    // TODO: check the limit
     //    self.limit = Some(limit);
     //    self
     //}
-    pub fn private(mut self, private: bool) -> Self {
-        self.private = Some(private);
+    /// Whether to include private messages the bot has access to (default: false)
+    pub fn include_private(mut self, include_private: bool) -> Self {
+        self.include_private = Some(include_private);
         self
     }
+    #[deprecated(note = "use `include_private`")]
+    pub fn private(self, private: bool) -> Self {
+        self.include_private(private)
+    }
+}
+
+/// Filters over a stream of messages, such as the one returned by [`GetChannelMessagesRequest::send`].
+/// Errors are always passed through.
+pub trait MessageStreamExt: Stream<Item = Result<ChatMessage>> + Sized {
+    /// Only keeps messages sent by `user`
+    fn by_author(self, user: &UserId) -> impl Stream<Item = Result<ChatMessage>> {
+        let user = user.clone();
+        self.filter(move |message| match message {
+            Ok(message) => message.created_by() == Some(&user),
+            Err(_) => true,
+        })
+    }
+    /// Only keeps messages whose content contains `text`
+    fn containing(self, text: &str) -> impl Stream<Item = Result<ChatMessage>> {
+        let text = text.to_owned();
+        self.filter(move |message| match message {
+            Ok(message) => message.content().contains(&text),
+            Err(_) => true,
+        })
+    }
+    /// Only keeps messages created between `start` and `end` (inclusive).
+    ///
+    /// History is streamed newest first, so newer messages are skipped and the stream
+    /// ends at the first message older than `start`.
+    fn between<S: TimeZone, E: TimeZone>(
+        self,
+        start: DateTime<S>,
+        end: DateTime<E>,
+    ) -> impl Stream<Item = Result<ChatMessage>> {
+        let start = start.with_timezone(&Utc);
+        let end = end.with_timezone(&Utc);
+        self.skip_while(move |message| match message {
+            Ok(message) => *message.created_at() > end,
+            Err(_) => false,
+        })
+        .take_while(move |message| match message {
+            Ok(message) => *message.created_at() >= start,
+            Err(_) => true,
+        })
+    }
 }
+impl<S: Stream<Item = Result<ChatMessage>>> MessageStreamExt for S {}
 
 enum ChannelMessageStream<'a> {
     Uninitialized(GetChannelMessagesRequest<'a>),
     Iterating {
-        client: Client,
+        client: Http,
         channel: &'a ChannelId,
-        after: Option<String>,
-        private: Option<bool>,
+        after: Option<DateTime<Utc>>,
+        include_private: Option<bool>,
         messages: Vec<ChatMessage>,
     },
     Transition,
 }
 impl<'a> ChannelMessageStream<'a> {
-    fn iter(request: GetChannelMessagesRequest) -> impl Stream<Item = Result<ChatMessage>> + '_ {
+    fn iter(
+        request: GetChannelMessagesRequest<'_>,
+    ) -> impl Stream<Item = Result<ChatMessage>> + '_ {
         stream! {
             let mut state = ChannelMessageStream::Uninitialized(request);
 
-            loop {
+            'pages: loop {
                 match mem::replace(&mut state, ChannelMessageStream::Transition) {
                     ChannelMessageStream::Uninitialized(request) => {
                         let client = request.client.clone();
                         let channel = request.channel;
-                        let after = request.after.clone();
-                        let private = request.private;
+                        let after = request.after;
+                        let include_private = request.include_private;
                         let messages = request.send_part().await?;
                         state = ChannelMessageStream::Iterating {
                             client,
                             channel,
                             after,
-                            private,
+                            include_private,
                             messages,
                         };
                         continue
                     },
-                    ChannelMessageStream::Iterating {client, channel, after, private, messages} => {
+                    ChannelMessageStream::Iterating {client, channel, after, include_private, messages} => {
                         let mut last_message = None;
                         for message in messages {
+                            // Pages walk backwards in time, so everything from here on is out of range
+                            if after.is_some_and(|after| message.created_at <= after) {
+                                break 'pages;
+                            }
                             last_message = Some(message.created_at);
                             yield Ok(message);
                         }
                         if let Some(last_message) = last_message {
                             let mut request = GetChannelMessagesRequest::new(client, channel).before(last_message);
                             if let Some(after) = after {
-                                request = request.after(after.parse::<DateTime<Utc>>().unwrap());
+                                request = request.after(after);
                             }
-                            if let Some(private) = private {
-                                request = request.private(private);
+                            if let Some(include_private) = include_private {
+                                request = request.include_private(include_private);
                             }
                             state = ChannelMessageStream::Uninitialized(request);
                             continue;
@@ -630,7 +1098,7 @@ impl<'a> ChannelMessageStream<'a> {
 
 #[derive(Debug)]
 pub struct GetMessageRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     message: &'a MessageId,
 }
@@ -640,7 +1108,7 @@ struct GetMessageResponse {
     message: ChatMessage,
 }
 impl<'a> GetMessageRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, message: &'a MessageId) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, message: &'a MessageId) -> Self {
         Self {
             client,
             channel,
@@ -648,17 +1116,34 @@ impl<'a> GetMessageRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<ChatMessage> {
-        let url: Url = format!(
-            "{API_BASE}/channels/{}/messages/{}",
-            self.channel, self.message
-        )
-        .parse()
-        .unwrap();
-        let request = self.client.get(url).build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        let request = self
+            .client
+            .get(Route::ChannelMessage {
+                channel: self.channel,
+                message: self.message,
+            })
+            .build()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ChatMessage, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self
+            .client
+            .execute(request)
+            .await?
+            .check_status()
+            .map_err(|e| e.not_found("message", self.message))?;
+        let meta = ResponseMeta::new(&response);
         let message: GetMessageResponse = response.json().await?;
 
-        Ok(message.message)
+        Ok((message.message, meta))
     }
 }
 
@@ -669,62 +1154,141 @@ struct UpdateMessageResponse {
 }
 #[derive(Debug, Serialize, Deserialize)]
 struct UpdateMessageRequestBody<'a> {
-    content: &'a str,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    embeds: Vec<ChatEmbed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<Cow<'a, str>>,
+    /// Replaces all the embeds when set, an empty list removes them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embeds: Option<Vec<ChatEmbed>>,
+    /// Replaces the hidden link previews when set, an empty list shows them all again
+    #[serde(rename = "hiddenLinkPreviewUrls")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hidden_link_previews: Option<Vec<String>>,
 }
+/// Edits a message, replacing the fields that are set and leaving the others as they are.
+///
+/// Whether a message is private or silent and what it replies to are set when it's created
+/// and can't be edited; editing keeps them.
 #[derive(Debug)]
 pub struct UpdateMessageRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     message: &'a MessageId,
     content: UpdateMessageRequestBody<'a>,
 }
 impl<'a> UpdateMessageRequest<'a> {
     pub fn new(
-        client: Client,
+        client: Http,
         channel: &'a ChannelId,
         message: &'a MessageId,
-        content: &'a str,
+        content: impl Into<Cow<'a, str>>,
     ) -> Self {
+        Self::edit(client, channel, message).content(content)
+    }
+    /// Edit that changes nothing yet, for edits that only touch the embeds
+    pub fn edit(client: Http, channel: &'a ChannelId, message: &'a MessageId) -> Self {
         Self {
             client,
             channel,
             message,
             content: UpdateMessageRequestBody {
-                content,
-                embeds: Vec::new(),
+                content: None,
+                embeds: None,
+                hidden_link_previews: None,
             },
         }
     }
     pub async fn send(self) -> Result<ChatMessage> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        match &self.content.content {
+            Some(content) => {
+                let length = content.chars().count();
+                if length > MAX_CONTENT_LENGTH {
+                    return Err(MessageError::ContentTooLong { length }.into());
+                }
+            }
+            None if self.content.embeds.is_none()
+                && self.content.hidden_link_previews.is_none() =>
+            {
+                return Err(Error::Validation {
+                    field: "content",
+                    constraint: Constraint::Required,
+                    value: String::new(),
+                });
+            }
+            None => {}
+        }
         let request = self
             .client
-            .put(format!(
-                "{API_BASE}/channels/{}/messages/{}",
-                self.channel, self.message
-            ))
+            .put(Route::ChannelMessage {
+                channel: self.channel,
+                message: self.message,
+            })
             .json(&self.content)
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(ChatMessage, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let message: UpdateMessageResponse = response.json().await?;
 
-        Ok(message.message)
+        Ok((message.message, meta))
     }
+    /// Replaces the content of the message
+    pub fn content(mut self, content: impl Into<Cow<'a, str>>) -> Self {
+        self.content.content = Some(content.into());
+        self
+    }
+    /// Adds an embed to the ones set so far. Embeds replace those the message has, they are
+    /// never appended to them.
     pub fn add_embed(mut self, embed: ChatEmbed) -> Self {
-        self.content.embeds.push(embed);
+        self.content.embeds.get_or_insert_with(Vec::new).push(embed);
+        self
+    }
+    /// Replaces the embeds of the message
+    pub fn set_embeds(mut self, embeds: Vec<ChatEmbed>) -> Self {
+        self.content.embeds = Some(embeds);
+        self
+    }
+    /// Removes all the embeds of the message
+    pub fn clear_embeds(self) -> Self {
+        self.set_embeds(Vec::new())
+    }
+    /// Shows `url`, a link in the content, without a preview. Like embeds, the hidden links
+    /// replace those set before, so links hidden at creation have to be hidden again.
+    ///
+    /// The URL is sent as given, so it has to be written the same way as in the content.
+    pub fn hide_link_preview(mut self, url: impl Into<String>) -> Result<Self> {
+        let url = url.into();
+        check_url(url.as_str())?;
+        self.content
+            .hidden_link_previews
+            .get_or_insert_with(Vec::new)
+            .push(url);
+        Ok(self)
+    }
+    /// Shows the previews of all the links again
+    pub fn show_link_previews(mut self) -> Self {
+        self.content.hidden_link_previews = Some(Vec::new());
         self
     }
 }
 
 #[derive(Debug)]
 pub struct DeleteMessageRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     message: &'a MessageId,
 }
 impl<'a> DeleteMessageRequest<'a> {
-    pub fn new(client: Client, channel: &'a ChannelId, message: &'a MessageId) -> Self {
+    pub fn new(client: Http, channel: &'a ChannelId, message: &'a MessageId) -> Self {
         Self {
             client,
             channel,
@@ -732,15 +1296,33 @@ impl<'a> DeleteMessageRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<()> {
+        self.send_with_meta().await.map(|_| ())
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .delete(format!(
-                "{API_BASE}/channels/{}/messages/{}",
-                self.channel, self.message
-            ))
+            .delete(Route::ChannelMessage {
+                channel: self.channel,
+                message: self.message,
+            })
             .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
 
-        Ok(())
+        Ok(request)
     }
+    pub async fn send_with_meta(self) -> Result<ResponseMeta> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+
+        Ok(meta)
+    }
+}
+
+/// Checks that `url` parses, for URLs that are still sent as written
+fn check_url(url: impl IntoUrl) -> Result<()> {
+    url.into_url()?;
+    Ok(())
 }
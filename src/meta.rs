@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use reqwest::header::{self, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Response, StatusCode};
+
+static REQUEST_ID: &str = "x-request-id";
+
+/// Status and headers of the response a request was answered with
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    status: StatusCode,
+    headers: HeaderMap,
+}
+impl ResponseMeta {
+    pub(crate) fn new(response: &Response) -> Self {
+        let mut headers = response.headers().clone();
+        headers.remove(header::SET_COOKIE);
+        Self {
+            status: response.status(),
+            headers,
+        }
+    }
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+    /// Response headers, minus cookies
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+    pub fn header(&self, name: impl AsRef<str>) -> Option<&HeaderValue> {
+        let name: HeaderName = name.as_ref().parse().ok()?;
+        self.headers.get(name)
+    }
+    /// ID the server assigned to the request, useful when reporting issues
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get(REQUEST_ID)?.to_str().ok()
+    }
+    /// How long the server asked to wait before retrying, when rate limited
+    pub fn retry_after(&self) -> Option<Duration> {
+        let seconds = self.headers.get(header::RETRY_AFTER)?.to_str().ok()?;
+        seconds.trim().parse().ok().map(Duration::from_secs)
+    }
+}
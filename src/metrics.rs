@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use reqwest::{Method, StatusCode};
+
+/// Destination for the client's metrics, set with [`GuildedClientBuilder::metrics`](crate::GuildedClientBuilder::metrics).
+///
+/// Routes are templates with the IDs replaced (e.g. `/channels/:id/messages`), so they can be
+/// used as labels without creating a series per channel.
+pub trait MetricsSink: Send + Sync {
+    /// A request finished, `status` is `None` when no response was received
+    fn request(&self, method: &Method, route: &str, status: Option<StatusCode>, elapsed: Duration);
+    /// A request was rate limited and waits `wait` before it's retried
+    fn rate_limit_wait(&self, _wait: Duration) {}
+    /// An event of `event_type` (e.g. `ChatMessageCreated`) was received from the gateway
+    fn gateway_event(&self, _event_type: &str) {}
+}
+
+#[cfg(feature = "prometheus")]
+pub use self::exporter::PrometheusSink;
+
+#[cfg(feature = "prometheus")]
+mod exporter {
+    use std::time::Duration;
+
+    use prometheus::{
+        Histogram, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, Result,
+    };
+    use reqwest::{Method, StatusCode};
+
+    use super::MetricsSink;
+
+    /// [`MetricsSink`] exporting the metrics to a Prometheus [`Registry`]:
+    ///
+    /// - `guilded_requests_total` by `method`, `route` and `status` (`none` without a response)
+    /// - `guilded_request_errors_total` by `method` and `route`, for error statuses and failed requests
+    /// - `guilded_request_duration_seconds` by `method` and `route`
+    /// - `guilded_rate_limit_wait_seconds`
+    /// - `guilded_gateway_events_total` by `event_type`
+    #[derive(Debug, Clone)]
+    pub struct PrometheusSink {
+        requests: IntCounterVec,
+        errors: IntCounterVec,
+        durations: HistogramVec,
+        rate_limit_waits: Histogram,
+        gateway_events: IntCounterVec,
+    }
+    impl PrometheusSink {
+        /// Creates the metrics and registers them in `registry`
+        pub fn new(registry: &Registry) -> Result<Self> {
+            let sink = Self {
+                requests: IntCounterVec::new(
+                    Opts::new("guilded_requests_total", "Requests sent to the Guilded API"),
+                    &["method", "route", "status"],
+                )?,
+                errors: IntCounterVec::new(
+                    Opts::new(
+                        "guilded_request_errors_total",
+                        "Requests that failed or got an error status",
+                    ),
+                    &["method", "route"],
+                )?,
+                durations: HistogramVec::new(
+                    HistogramOpts::new(
+                        "guilded_request_duration_seconds",
+                        "Time until the API responded",
+                    ),
+                    &["method", "route"],
+                )?,
+                rate_limit_waits: Histogram::with_opts(HistogramOpts::new(
+                    "guilded_rate_limit_wait_seconds",
+                    "Time waited after being rate limited",
+                ))?,
+                gateway_events: IntCounterVec::new(
+                    Opts::new(
+                        "guilded_gateway_events_total",
+                        "Events received from the gateway",
+                    ),
+                    &["event_type"],
+                )?,
+            };
+            registry.register(Box::new(sink.requests.clone()))?;
+            registry.register(Box::new(sink.errors.clone()))?;
+            registry.register(Box::new(sink.durations.clone()))?;
+            registry.register(Box::new(sink.rate_limit_waits.clone()))?;
+            registry.register(Box::new(sink.gateway_events.clone()))?;
+
+            Ok(sink)
+        }
+    }
+    impl MetricsSink for PrometheusSink {
+        fn request(
+            &self,
+            method: &Method,
+            route: &str,
+            status: Option<StatusCode>,
+            elapsed: Duration,
+        ) {
+            let method = method.as_str();
+            let code =
+                status.map_or_else(|| "none".to_owned(), |status| status.as_u16().to_string());
+            self.requests
+                .with_label_values(&[method, route, &code])
+                .inc();
+            if status.is_none_or(|status| status.is_client_error() || status.is_server_error()) {
+                self.errors.with_label_values(&[method, route]).inc();
+            }
+            self.durations
+                .with_label_values(&[method, route])
+                .observe(elapsed.as_secs_f64());
+        }
+        fn rate_limit_wait(&self, wait: Duration) {
+            self.rate_limit_waits.observe(wait.as_secs_f64());
+        }
+        fn gateway_event(&self, event_type: &str) {
+            self.gateway_events.with_label_values(&[event_type]).inc();
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::future::Future;
+
+use opentelemetry::global::{self, BoxedSpan};
+use opentelemetry::trace::{FutureExt, Span, SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{Context, KeyValue};
+use reqwest::{Request, Response};
+
+use crate::routes::template;
+
+/// Name the spans are reported under
+const TRACER: &str = "guilded-rs";
+
+/// Client span of a single API call, a child of the caller's current context
+pub(crate) struct RequestSpan(BoxedSpan);
+impl RequestSpan {
+    /// Starts the span of `request`, named like `GET /channels/:id/messages`
+    pub(crate) fn start(request: &Request) -> Self {
+        let url = request.url();
+        let route = template(url);
+        let mut attributes = vec![
+            KeyValue::new("http.request.method", request.method().to_string()),
+            KeyValue::new("http.route", route.clone()),
+            KeyValue::new("url.full", url.to_string()),
+        ];
+        if let Some(host) = url.host_str() {
+            attributes.push(KeyValue::new("server.address", host.to_owned()));
+        }
+        let tracer = global::tracer(TRACER);
+        let span = tracer
+            .span_builder(format!("{} {route}", request.method()))
+            .with_kind(SpanKind::Client)
+            .with_attributes(attributes)
+            .start(&tracer);
+        Self(span)
+    }
+    /// Records the outcome of the request and ends the span
+    pub(crate) fn end(mut self, response: &reqwest::Result<Response>) {
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                self.0.set_attribute(KeyValue::new(
+                    "http.response.status_code",
+                    i64::from(status.as_u16()),
+                ));
+                if status.is_client_error() || status.is_server_error() {
+                    self.0
+                        .set_attribute(KeyValue::new("error.type", status.as_str().to_owned()));
+                    self.0.set_status(Status::error(status.to_string()));
+                }
+            }
+            Err(e) => {
+                self.0.set_attribute(KeyValue::new("error.type", "request"));
+                self.0.set_status(Status::error(e.to_string()));
+            }
+        }
+        self.0.end();
+    }
+}
+
+/// Runs a user handler in a span of its own, a child of `parent`, so the API calls it makes are
+/// traced under it
+pub(crate) fn handler<Fut: Future>(
+    name: &'static str,
+    parent: &Context,
+    handler: Fut,
+) -> impl Future<Output = Fut::Output> {
+    let tracer = global::tracer(TRACER);
+    let span = tracer.start_with_context(name, parent);
+    handler.with_context(parent.with_span(span))
+}
@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::channel::ChannelId;
+use crate::error::{Error, ResponseExt, Result};
+use crate::http::Http;
+use crate::message::{ChatMessage, CreateMessageRequest, CreateMessageResponse};
+use crate::routes::Route;
+use crate::schedule::ScheduledTask;
+use crate::GuildedClient;
+
+/// Message waiting in an [`Outbox`] to be sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedMessage {
+    channel: ChannelId,
+    body: serde_json::Value,
+}
+impl QueuedMessage {
+    fn new(request: &CreateMessageRequest<'_>) -> Result<Self> {
+        Ok(Self {
+            channel: *request.channel(),
+            body: serde_json::to_value(request)?,
+        })
+    }
+    pub fn channel(&self) -> &ChannelId {
+        &self.channel
+    }
+    /// Body of the create message request, as it will be sent
+    pub fn body(&self) -> &serde_json::Value {
+        &self.body
+    }
+}
+
+/// Storage for the messages queued in an [`Outbox`], oldest first
+pub trait OutboxStore: Send + Sync {
+    fn push(&self, message: QueuedMessage) -> Result<()>;
+    fn front(&self) -> Result<Option<QueuedMessage>>;
+    fn pop_front(&self) -> Result<()>;
+}
+
+/// Keeps the queue in memory, so it's lost when the process exits
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    queue: StdMutex<VecDeque<QueuedMessage>>,
+}
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl OutboxStore for MemoryStore {
+    fn push(&self, message: QueuedMessage) -> Result<()> {
+        self.queue.lock().unwrap().push_back(message);
+        Ok(())
+    }
+    fn front(&self) -> Result<Option<QueuedMessage>> {
+        Ok(self.queue.lock().unwrap().front().cloned())
+    }
+    fn pop_front(&self) -> Result<()> {
+        self.queue.lock().unwrap().pop_front();
+        Ok(())
+    }
+}
+
+/// Keeps the queue in a file, one JSON message per line, so it survives restarts
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+}
+impl FileStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+    fn read(&self) -> Result<String> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+impl OutboxStore for FileStore {
+    fn push(&self, message: QueuedMessage) -> Result<()> {
+        let mut line = serde_json::to_vec(&message)?;
+        line.push(b'\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+    fn front(&self) -> Result<Option<QueuedMessage>> {
+        let contents = self.read()?;
+        match contents.lines().find(|line| !line.is_empty()) {
+            Some(line) => Ok(Some(serde_json::from_str(line)?)),
+            None => Ok(None),
+        }
+    }
+    fn pop_front(&self) -> Result<()> {
+        let contents = self.read()?;
+        let mut lines = contents.lines().filter(|line| !line.is_empty());
+        lines.next();
+        let rest: String = lines.map(|line| format!("{line}\n")).collect();
+        // Written next to the queue and renamed over it, so a crash never leaves a truncated file
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        let mut file = File::create(&temp)?;
+        file.write_all(rest.as_bytes())?;
+        file.sync_data()?;
+        fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Queue that holds on to messages while the API is unreachable and sends them in order once it's back.
+///
+/// Network failures, server errors and rate limits queue a message to be tried again. Messages
+/// the API rejects outright (bad request, missing permissions, deleted channel, ...) would fail
+/// the same way on every try, so they're taken out of the queue and kept for
+/// [`Outbox::take_rejected`] instead of holding up the messages behind them.
+#[derive(Clone)]
+pub struct Outbox {
+    client: GuildedClient,
+    store: Arc<dyn OutboxStore>,
+    rejected: Arc<StdMutex<Vec<(QueuedMessage, Error)>>>,
+    /// Held while sending, so queued messages go out one at a time and in order
+    lock: Arc<Mutex<()>>,
+}
+impl Outbox {
+    pub(crate) fn new(client: GuildedClient, store: impl OutboxStore + 'static) -> Self {
+        Self {
+            client,
+            store: Arc::new(store),
+            rejected: Arc::default(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+    /// Sends the message, or queues it if the API can't take it right now.
+    ///
+    /// The message is checked like [`CreateMessageRequest::send`] does before anything is queued.
+    /// A [`split_long`](CreateMessageRequest::split_long) message is queued as one message per
+    /// part, and the last part is returned once they're all sent.
+    ///
+    /// Returns `None` when the message, or the rest of its parts, was queued. Messages with
+    /// attached images aren't queued, as the images are only uploaded on send.
+    pub async fn send(&self, request: CreateMessageRequest<'_>) -> Result<Option<ChatMessage>> {
+        if request.has_attachments() {
+            return request.send().await.map(Some);
+        }
+        request.validate()?;
+        let messages = match request.split_parts() {
+            Some(parts) => parts
+                .iter()
+                .map(QueuedMessage::new)
+                .collect::<Result<_>>()?,
+            None => vec![QueuedMessage::new(&request)?],
+        };
+        let _guard = self.lock.lock().await;
+        let mut messages = messages.into_iter();
+        // Anything still queued has to go first to keep the order
+        if !self.flush_locked().await? {
+            messages.try_for_each(|message| self.store.push(message))?;
+            return Ok(None);
+        }
+        let mut sent = None;
+        while let Some(message) = messages.next() {
+            match send_queued(&self.client, &message).await {
+                Ok(message) => sent = Some(message),
+                Err(e) if is_retryable(&e) => {
+                    self.store.push(message)?;
+                    messages.try_for_each(|message| self.store.push(message))?;
+                    return Ok(None);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(sent)
+    }
+    /// Sends the queued messages, oldest first.
+    ///
+    /// Returns whether the queue was emptied; `false` means the API is still unreachable.
+    /// Rejected messages are dropped from the queue, see [`Outbox::take_rejected`].
+    pub async fn flush(&self) -> Result<bool> {
+        let _guard = self.lock.lock().await;
+        self.flush_locked().await
+    }
+    /// Flushes the queue every `period` in the background
+    pub fn flush_every(&self, period: Duration) -> ScheduledTask {
+        let outbox = self.clone();
+        self.client.every(period, move |_| {
+            let outbox = outbox.clone();
+            async move {
+                let _ = outbox.flush().await;
+            }
+        })
+    }
+    async fn flush_locked(&self) -> Result<bool> {
+        while let Some(message) = self.store.front()? {
+            match send_queued(&self.client, &message).await {
+                Ok(_) => self.store.pop_front()?,
+                Err(e) if is_retryable(&e) => return Ok(false),
+                Err(e) => {
+                    self.store.pop_front()?;
+                    self.rejected.lock().unwrap().push((message, e));
+                }
+            }
+        }
+        Ok(true)
+    }
+    /// Queued messages the API rejected while flushing, with the error it gave, since the last call
+    pub fn take_rejected(&self) -> Vec<(QueuedMessage, Error)> {
+        std::mem::take(&mut *self.rejected.lock().unwrap())
+    }
+}
+impl std::fmt::Debug for Outbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Outbox")
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Whether the message may go through on a later try: it didn't reach the API, or the API
+/// couldn't take it right now
+fn is_retryable(e: &Error) -> bool {
+    match e {
+        Error::ReqwestError(e) => e.is_connect() || e.is_timeout(),
+        Error::RateLimited { .. } => true,
+        Error::Status(context) => context.status().is_server_error(),
+        _ => false,
+    }
+}
+
+/// Sends a queued message through the same per-channel turn and throttle as any other message
+async fn send_queued(client: &Http, message: &QueuedMessage) -> Result<ChatMessage> {
+    let _turn = client.send_turn(&message.channel).await;
+    if let Some(throttle) = client.throttle() {
+        throttle.acquire(&message.channel).await;
+    }
+    let request = client
+        .post(Route::ChannelMessages {
+            channel: &message.channel,
+        })
+        .json(&message.body)
+        .build()?;
+    let response = client.execute(request).await?.check_status()?;
+    let created: CreateMessageResponse = response.json().await?;
+
+    Ok(created.message)
+}
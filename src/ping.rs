@@ -0,0 +1,62 @@
+use std::time::{Duration, Instant};
+
+use reqwest::Request;
+
+use crate::dry_run::DryRun;
+use crate::error::{Error, ResponseExt, Result};
+use crate::http::Http;
+use crate::routes::Route;
+
+/// Result of [`PingRequest`]
+#[derive(Debug, Clone, Copy)]
+pub struct Ping {
+    latency: Duration,
+    authorized: bool,
+}
+impl Ping {
+    /// Time until the response headers arrived
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+    /// Whether the API accepted the bot token
+    pub fn authorized(&self) -> bool {
+        self.authorized
+    }
+}
+
+/// Health check that fetches the bot's own user, e.g. for readiness probes
+#[derive(Debug)]
+pub struct PingRequest {
+    client: Http,
+}
+impl PingRequest {
+    pub fn new(client: Http) -> Self {
+        Self { client }
+    }
+    /// Fails only if the API couldn't be reached or answered with an unexpected error;
+    /// a rejected token is reported through [`Ping::authorized`]
+    pub async fn send(self) -> Result<Ping> {
+        let request = self.build_request()?;
+        let start = Instant::now();
+        let response = self.client.execute(request).await?;
+        let latency = start.elapsed();
+        let authorized = match response.check_status() {
+            Ok(_) => true,
+            Err(Error::Unauthorized(_)) => false,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Ping {
+            latency,
+            authorized,
+        })
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        let request = self.client.get(Route::CurrentUser).build()?;
+
+        Ok(request)
+    }
+}
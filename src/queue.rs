@@ -0,0 +1,268 @@
+use std::collections::{HashMap, VecDeque};
+use std::result::Result as StdResult;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{watch, Mutex as AsyncMutex, OwnedMutexGuard};
+use tokio::time::Instant;
+
+use crate::channel::ChannelId;
+use crate::clock::{Clock, TokioClock};
+use crate::message::{ChatMessage, MAX_CONTENT_LENGTH};
+use crate::meta::ResponseMeta;
+
+/// One queue per channel, so messages to a channel are sent one at a time and in the order
+/// they were sent in, while different channels still send in parallel
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChannelQueues {
+    channels: Arc<Mutex<HashMap<ChannelId, Arc<AsyncMutex<()>>>>>,
+}
+impl ChannelQueues {
+    /// Waits for the messages queued before this one to be sent. The turn lasts until the returned guard is dropped.
+    pub(crate) async fn turn(&self, channel: &ChannelId) -> Turn {
+        let queue = {
+            let mut channels = self.channels.lock().unwrap();
+            channels.entry(*channel).or_default().clone()
+        };
+        // Tokio's mutex is fair, so waiters are served first come, first served
+        let guard = queue.lock_owned().await;
+        Turn {
+            queues: self.clone(),
+            channel: *channel,
+            guard: Some(guard),
+        }
+    }
+}
+
+pub(crate) struct Turn {
+    queues: ChannelQueues,
+    channel: ChannelId,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let mut channels = self.queues.channels.lock().unwrap();
+        drop(self.guard.take());
+        // Forget the queue once nobody is waiting on it, so idle channels don't pile up
+        if channels
+            .get(&self.channel)
+            .is_some_and(|queue| Arc::strong_count(queue) == 1)
+        {
+            channels.remove(&self.channel);
+        }
+    }
+}
+
+type Sent = Arc<(ChatMessage, ResponseMeta)>;
+
+/// Limits how many messages are sent to a channel per period, optionally merging messages
+/// that have to wait into one
+#[derive(Clone)]
+pub(crate) struct Throttle {
+    max: usize,
+    period: Duration,
+    coalesce: bool,
+    windows: Arc<Mutex<Windows>>,
+    clock: Arc<dyn Clock>,
+}
+impl std::fmt::Debug for Throttle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Throttle")
+            .field("max", &self.max)
+            .field("period", &self.period)
+            .field("coalesce", &self.coalesce)
+            .finish_non_exhaustive()
+    }
+}
+#[derive(Debug, Default)]
+struct Windows {
+    channels: HashMap<ChannelId, Window>,
+    /// When idle windows were last dropped
+    pruned: Option<Instant>,
+}
+#[derive(Debug, Default)]
+struct Window {
+    /// When the messages of the current period were sent, oldest first
+    sent: VecDeque<Instant>,
+    /// Messages waiting to be sent together
+    batch: Option<Batch>,
+}
+impl Window {
+    /// Whether nothing was sent within the last `period` and no batch is waiting to be sent, so
+    /// the window can be dropped without changing when later messages go out
+    fn is_idle(&self, now: Instant, period: Duration) -> bool {
+        let recent = self
+            .sent
+            .back()
+            .is_some_and(|&sent| now.duration_since(sent) < period);
+        let waiting = self
+            .batch
+            .as_ref()
+            .is_some_and(|batch| batch.done.has_changed().is_ok());
+        !recent && !waiting
+    }
+}
+#[derive(Debug)]
+struct Batch {
+    contents: Arc<Mutex<Vec<String>>>,
+    done: watch::Receiver<Option<Sent>>,
+}
+
+/// Outcome of [`Throttle::coalesce`]
+pub(crate) enum Coalesced {
+    /// The message may be sent now
+    Send,
+    /// The message waited and may be sent now, together with the contents others merged in;
+    /// the result has to be published with [`Leader::finish`]
+    Lead(Leader),
+    /// The message was merged into another one, which will be sent instead
+    Merged(watch::Receiver<Option<Sent>>),
+}
+pub(crate) struct Leader {
+    contents: Arc<Mutex<Vec<String>>>,
+    done: watch::Sender<Option<Sent>>,
+}
+impl Leader {
+    /// Content of the merged message, one original message per line
+    pub(crate) fn content(&self) -> String {
+        self.contents.lock().unwrap().join("\n")
+    }
+    /// Hands the sent message to the messages merged into it. On failure they send themselves.
+    pub(crate) fn finish(self, sent: Option<&(ChatMessage, ResponseMeta)>) {
+        if let Some(sent) = sent {
+            let _ = self.done.send(Some(Arc::new(sent.clone())));
+        }
+    }
+}
+
+impl Throttle {
+    pub(crate) fn new(max: usize, period: Duration, coalesce: bool) -> Self {
+        Self {
+            max: max.max(1),
+            period,
+            coalesce,
+            windows: Arc::default(),
+            clock: Arc::new(TokioClock),
+        }
+    }
+    pub(crate) fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+    /// Waits until another message may be sent to `channel`
+    pub(crate) async fn acquire(&self, channel: &ChannelId) {
+        loop {
+            let wait = {
+                let mut windows = self.windows.lock().unwrap();
+                let window = self.window(&mut windows, channel);
+                match self.try_send(window) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            self.clock.sleep(wait).await;
+        }
+    }
+    /// Like [`Throttle::acquire`], but if the message has to wait it's merged with the other
+    /// waiting messages when coalescing is enabled
+    pub(crate) async fn coalesce(&self, channel: &ChannelId, content: &str) -> Coalesced {
+        let (contents, done, wait) = {
+            let mut windows = self.windows.lock().unwrap();
+            let window = self.window(&mut windows, channel);
+            let wait = match self.try_send(window) {
+                Ok(()) => return Coalesced::Send,
+                Err(wait) => wait,
+            };
+            if !self.coalesce {
+                (None, None, wait)
+            } else if let Some(done) = merge(window, content) {
+                return Coalesced::Merged(done);
+            } else {
+                let contents = Arc::new(Mutex::new(vec![content.to_owned()]));
+                let (done, receiver) = watch::channel(None);
+                window.batch = Some(Batch {
+                    contents: contents.clone(),
+                    done: receiver,
+                });
+                (Some(contents), Some(done), wait)
+            }
+        };
+        let (Some(contents), Some(done)) = (contents, done) else {
+            self.acquire(channel).await;
+            return Coalesced::Send;
+        };
+        self.clock.sleep(wait).await;
+        loop {
+            let wait = {
+                let mut windows = self.windows.lock().unwrap();
+                let window = self.window(&mut windows, channel);
+                match self.try_send(window) {
+                    Ok(()) => {
+                        // Later messages start a new batch
+                        if window
+                            .batch
+                            .as_ref()
+                            .is_some_and(|batch| Arc::ptr_eq(&batch.contents, &contents))
+                        {
+                            window.batch = None;
+                        }
+                        return Coalesced::Lead(Leader { contents, done });
+                    }
+                    Err(wait) => wait,
+                }
+            };
+            self.clock.sleep(wait).await;
+        }
+    }
+    /// Window of `channel`, dropping those of channels that went idle once per period so the map
+    /// doesn't grow with every channel ever sent to
+    fn window<'w>(&self, windows: &'w mut Windows, channel: &ChannelId) -> &'w mut Window {
+        let now = self.clock.now();
+        if windows
+            .pruned
+            .is_none_or(|pruned| now.duration_since(pruned) >= self.period)
+        {
+            windows
+                .channels
+                .retain(|_, window| !window.is_idle(now, self.period));
+            windows.pruned = Some(now);
+        }
+        windows.channels.entry(*channel).or_default()
+    }
+    /// Records a send if the window has room, otherwise returns how long until it has
+    fn try_send(&self, window: &mut Window) -> StdResult<(), Duration> {
+        let now = self.clock.now();
+        while window
+            .sent
+            .front()
+            .is_some_and(|&sent| now.duration_since(sent) >= self.period)
+        {
+            window.sent.pop_front();
+        }
+        match window.sent.front() {
+            Some(&oldest) if window.sent.len() >= self.max => {
+                Err(self.period.saturating_sub(now.duration_since(oldest)))
+            }
+            _ => {
+                window.sent.push_back(now);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Adds `content` to the batch waiting in `window`, if there is one with room left
+fn merge(window: &Window, content: &str) -> Option<watch::Receiver<Option<Sent>>> {
+    // A batch whose leader was dropped can't be joined anymore
+    let batch = window
+        .batch
+        .as_ref()
+        .filter(|batch| batch.done.has_changed().is_ok())?;
+    let mut contents = batch.contents.lock().unwrap();
+    let length: usize = contents.iter().map(|c| c.chars().count() + 1).sum();
+    if length + content.chars().count() > MAX_CONTENT_LENGTH {
+        return None;
+    }
+    contents.push(content.to_owned());
+    Some(batch.done.clone())
+}
@@ -0,0 +1,77 @@
+use std::fmt::Display;
+
+use reqwest::{Client, Method, Request};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
+use crate::meta::ResponseMeta;
+
+/// Request to an arbitrary API route, for endpoints this crate doesn't wrap yet.
+///
+/// `path` is relative to the API base, e.g. `/servers/{serverId}/members`.
+#[derive(Debug)]
+pub struct RawRequest<'a> {
+    client: Http,
+    method: Method,
+    path: &'a str,
+    query: Vec<(String, String)>,
+    body: Option<serde_json::Value>,
+}
+impl<'a> RawRequest<'a> {
+    pub fn new(client: Http, method: Method, path: &'a str) -> Self {
+        Self {
+            client,
+            method,
+            path,
+            query: Vec::new(),
+            body: None,
+        }
+    }
+    pub async fn send<T: DeserializeOwned>(self) -> Result<T> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub async fn send_with_meta<T: DeserializeOwned>(self) -> Result<(T, ResponseMeta)> {
+        let response = self.execute().await?;
+        let meta = ResponseMeta::new(&response);
+        let value = response.json().await?;
+
+        Ok((value, meta))
+    }
+    /// Sends the request without reading a body from the response
+    pub async fn send_empty(self) -> Result<ResponseMeta> {
+        let response = self.execute().await?;
+
+        Ok(ResponseMeta::new(&response))
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        let separator = if self.path.starts_with('/') { "" } else { "/" };
+        let url = self.client.url(&format!("{separator}{}", self.path));
+        let mut request =
+            Client::request(&self.client, self.method.clone(), url).query(&self.query);
+        if let Some(body) = &self.body {
+            request = request.json(body);
+        }
+
+        Ok(request.build()?)
+    }
+    async fn execute(self) -> Result<reqwest::Response> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+
+        Ok(response)
+    }
+    pub fn query(mut self, name: &str, value: impl Display) -> Self {
+        self.query.push((name.to_owned(), value.to_string()));
+        self
+    }
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Result<Self> {
+        self.body = Some(serde_json::to_value(body)?);
+        Ok(self)
+    }
+}
@@ -4,17 +4,24 @@ use std::result::Result as StdResult;
 use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
 
 use crate::channel::ChannelId;
+#[cfg(feature = "docs")]
 use crate::docs::DocId;
-use crate::error::Result;
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+#[cfg(feature = "forums")]
 use crate::forums::ForumId;
+use crate::http::Http;
+use crate::id;
+#[cfg(feature = "list")]
 use crate::list::ListId;
 use crate::member::{ServerId, UserId};
 use crate::message::{MessageId, WebhookId};
-use crate::API_BASE;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 #[repr(transparent)]
@@ -24,7 +31,7 @@ impl<'de> Deserialize<'de> for EmoteId {
     where
         D: serde::Deserializer<'de>,
     {
-        u32::deserialize(deserializer).map(Self)
+        id::deserialize_u32(deserializer).map(Self)
     }
 }
 impl Serialize for EmoteId {
@@ -36,7 +43,7 @@ impl Serialize for EmoteId {
     }
 }
 impl EmoteId {
-    pub fn new(reaction: u32) -> Self {
+    pub const fn new(reaction: u32) -> Self {
         Self(reaction)
     }
 }
@@ -86,12 +93,73 @@ pub struct Reaction {
     #[serde(rename = "createdByWebhookId")]
     webhook: Option<WebhookId>,
 }
+impl Reaction {
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+}
+
+/// Emote used in a reaction, as sent with the reaction events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Emote {
+    id: EmoteId,
+    name: String,
+    /// Image of the emote
+    url: String,
+}
+impl Emote {
+    pub fn id(&self) -> EmoteId {
+        self.id
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// Reaction a user added to or removed from a message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MessageReaction {
+    #[serde(rename = "channelId")]
+    channel: ChannelId,
+    #[serde(rename = "messageId")]
+    message: MessageId,
+    #[serde(rename = "createdBy")]
+    user: UserId,
+    emote: Emote,
+}
+impl MessageReaction {
+    pub fn channel(&self) -> ChannelId {
+        self.channel
+    }
+    pub fn message(&self) -> MessageId {
+        self.message
+    }
+    /// User who reacted
+    pub fn user(&self) -> &UserId {
+        &self.user
+    }
+    pub fn emote(&self) -> &Emote {
+        &self.emote
+    }
+}
 
 #[derive(Debug)]
 pub enum ContentId<'a> {
     Channel(&'a ChannelId),
+    #[cfg(feature = "docs")]
     Doc(&'a DocId),
+    #[cfg(feature = "forums")]
     Forum(&'a ForumId),
+    #[cfg(feature = "list")]
     List(&'a ListId),
     Message(&'a MessageId),
 }
@@ -102,8 +170,11 @@ impl<'a> Serialize for ContentId<'a> {
     {
         match self {
             ContentId::Channel(channel) => channel.serialize(serializer),
+            #[cfg(feature = "docs")]
             ContentId::Doc(doc) => doc.serialize(serializer),
+            #[cfg(feature = "forums")]
             ContentId::Forum(forum) => forum.serialize(serializer),
+            #[cfg(feature = "list")]
             ContentId::List(list) => list.serialize(serializer),
             ContentId::Message(message) => message.serialize(serializer),
         }
@@ -114,16 +185,19 @@ impl<'a> From<&'a ChannelId> for ContentId<'a> {
         Self::Channel(channel)
     }
 }
+#[cfg(feature = "docs")]
 impl<'a> From<&'a DocId> for ContentId<'a> {
     fn from(doc: &'a DocId) -> Self {
         Self::Doc(doc)
     }
 }
+#[cfg(feature = "forums")]
 impl<'a> From<&'a ForumId> for ContentId<'a> {
     fn from(forum: &'a ForumId) -> Self {
         Self::Forum(forum)
     }
 }
+#[cfg(feature = "list")]
 impl<'a> From<&'a ListId> for ContentId<'a> {
     fn from(list: &'a ListId) -> Self {
         ContentId::List(list)
@@ -138,8 +212,11 @@ impl<'a> Display for ContentId<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Channel(channel) => channel.fmt(f),
+            #[cfg(feature = "docs")]
             Self::Doc(doc) => doc.fmt(f),
+            #[cfg(feature = "forums")]
             Self::Forum(forum) => forum.fmt(f),
+            #[cfg(feature = "list")]
             Self::List(list) => list.fmt(f),
             Self::Message(message) => message.fmt(f),
         }
@@ -148,14 +225,14 @@ impl<'a> Display for ContentId<'a> {
 
 #[derive(Debug)]
 pub struct AddReactionRequest<'a> {
-    client: Client,
+    client: Http,
     channel: &'a ChannelId,
     content: ContentId<'a>,
     emote: &'a EmoteId,
 }
 impl<'a> AddReactionRequest<'a> {
     pub fn new<C: Into<ContentId<'a>>>(
-        client: Client,
+        client: Http,
         channel: &'a ChannelId,
         content: C,
         emote: &'a EmoteId,
@@ -168,15 +245,28 @@ impl<'a> AddReactionRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<()> {
+        self.send_with_meta().await.map(|_| ())
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .put(format!(
-                "{API_BASE}/channels/{}/content/{}/emotes/{}",
-                self.channel, self.content, self.emote
-            ))
+            .put(Route::ContentEmote {
+                channel: self.channel,
+                content: &self.content,
+                emote: self.emote,
+            })
             .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
 
-        Ok(())
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<ResponseMeta> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+
+        Ok(meta)
     }
 }
@@ -3,12 +3,16 @@ use std::ops::Deref;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
-use reqwest::Client;
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
+use crate::id;
 use crate::member::{ServerId, UserId};
-use crate::API_BASE;
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 #[repr(transparent)]
@@ -19,7 +23,7 @@ impl<'de> Deserialize<'de> for RoleId {
     where
         D: serde::Deserializer<'de>,
     {
-        u32::deserialize(deserializer).map(Self)
+        id::deserialize_u32(deserializer).map(Self)
     }
 }
 impl Serialize for RoleId {
@@ -71,13 +75,13 @@ impl FromStr for RoleId {
 
 #[derive(Debug)]
 pub struct AssignRoleRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
     role: &'a RoleId,
 }
 impl<'a> AssignRoleRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId, role: &'a RoleId) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId, role: &'a RoleId) -> Self {
         Self {
             client,
             server,
@@ -86,28 +90,41 @@ impl<'a> AssignRoleRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<()> {
+        self.send_with_meta().await.map(|_| ())
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .put(format!(
-                "{API_BASE}/servers/{}/members/{}/roles/{}",
-                self.server, self.user, self.role
-            ))
+            .put(Route::MemberRole {
+                server: self.server,
+                user: self.user,
+                role: self.role,
+            })
             .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
 
-        Ok(())
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<ResponseMeta> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+
+        Ok(meta)
     }
 }
 
 #[derive(Debug)]
 pub struct RemoveRoleRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
     role: &'a RoleId,
 }
 impl<'a> RemoveRoleRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId, role: &'a RoleId) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId, role: &'a RoleId) -> Self {
         Self {
             client,
             server,
@@ -116,16 +133,29 @@ impl<'a> RemoveRoleRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<()> {
+        self.send_with_meta().await.map(|_| ())
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .delete(format!(
-                "{API_BASE}/servers/{}/members/{}/roles/{}",
-                self.server, self.user, self.role
-            ))
+            .delete(Route::MemberRole {
+                server: self.server,
+                user: self.user,
+                role: self.role,
+            })
             .build()?;
-        let _response = self.client.execute(request).await?;
 
-        Ok(())
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<ResponseMeta> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+
+        Ok(meta)
     }
 }
 
@@ -136,12 +166,12 @@ struct GetMemberRolesResponse {
 }
 #[derive(Debug)]
 pub struct GetMemberRolesRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
 }
 impl<'a> GetMemberRolesRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId) -> Self {
         Self {
             client,
             server,
@@ -149,16 +179,28 @@ impl<'a> GetMemberRolesRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<Vec<RoleId>> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let request = self
             .client
-            .get(format!(
-                "{API_BASE}/servers/{}/members/{}/roles",
-                self.server, self.user
-            ))
+            .get(Route::MemberRoles {
+                server: self.server,
+                user: self.user,
+            })
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(Vec<RoleId>, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let roles: GetMemberRolesResponse = response.json().await?;
 
-        Ok(roles.roles)
+        Ok((roles.roles, meta))
     }
 }
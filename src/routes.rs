@@ -0,0 +1,323 @@
+use std::fmt::Display;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use reqwest::Url;
+use serde::Deserialize;
+
+#[cfg(feature = "calendar")]
+use crate::calendar::CalendarEventId;
+use crate::channel::ChannelId;
+#[cfg(feature = "docs")]
+use crate::docs::DocId;
+use crate::groups::GroupId;
+#[cfg(feature = "list")]
+use crate::list::ListId;
+use crate::member::{ServerId, UserId};
+use crate::message::MessageId;
+use crate::reactions::{ContentId, EmoteId};
+use crate::roles::RoleId;
+#[cfg(feature = "social")]
+use crate::social::SocialMediaType;
+use crate::API_HOST;
+
+/// Characters that would end or change the meaning of a path segment
+const SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'.')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Percent-encodes an ID so it always stays a single path segment, e.g. a user ID of `../bans`
+fn segment(id: &impl Display) -> String {
+    let id = id.to_string();
+    let encoded = utf8_percent_encode(&id, SEGMENT).to_string();
+    // URL parsing still resolves `%2E` and `%2E%2E` as `.` and `..`, so an ID of only dots is
+    // encoded once more: it's then sent as the literal `%2E%2E`, which the API won't find
+    if matches!(id.as_str(), "." | "..") {
+        return utf8_percent_encode(&encoded, SEGMENT).to_string();
+    }
+    encoded
+}
+
+/// Version of the API the routes are sent to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "String")]
+pub enum ApiVersion {
+    #[default]
+    V1,
+    /// Any other version, by the name used in its path (e.g. `v2` for `/api/v2`)
+    Other(String),
+}
+impl ApiVersion {
+    /// Path the API's routes are under, e.g. `/api/v1`
+    pub fn path(&self) -> String {
+        format!("/api/{self}")
+    }
+    /// URL the API's routes are under on the public API, e.g. `https://www.guilded.gg/api/v1`
+    pub fn base_url(&self) -> String {
+        format!("{API_HOST}{}", self.path())
+    }
+}
+impl Display for ApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V1 => write!(f, "v1"),
+            Self::Other(version) => write!(f, "{version}"),
+        }
+    }
+}
+impl From<String> for ApiVersion {
+    fn from(version: String) -> Self {
+        match version.as_str() {
+            "v1" => Self::V1,
+            _ => Self::Other(version),
+        }
+    }
+}
+
+/// Replaces the IDs in the path of `url` with `:id`, e.g. `/channels/:id/messages`.
+///
+/// API paths alternate between a resource name and an ID (`/servers/{id}/members/{id}/xp`).
+pub(crate) fn template(url: &Url) -> String {
+    let path = url.path();
+    // Drops the `/api/{version}` prefix
+    let path = path
+        .strip_prefix("/api/")
+        .and_then(|path| path.find('/').map(|version| &path[version..]))
+        .unwrap_or(path);
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .enumerate()
+        .map(|(i, segment)| if i % 2 == 1 { ":id" } else { segment })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Endpoints of the API, so the URL of each is only spelled out once
+#[derive(Debug, Clone, Copy)]
+pub enum Route<'a> {
+    Channels,
+    Channel {
+        channel: &'a ChannelId,
+    },
+    ChannelMessages {
+        channel: &'a ChannelId,
+    },
+    ChannelMessage {
+        channel: &'a ChannelId,
+        message: &'a MessageId,
+    },
+    #[cfg(feature = "calendar")]
+    CalendarEventRsvps {
+        channel: &'a ChannelId,
+        event: &'a CalendarEventId,
+    },
+    ContentEmote {
+        channel: &'a ChannelId,
+        content: &'a ContentId<'a>,
+        emote: &'a EmoteId,
+    },
+    #[cfg(feature = "docs")]
+    Docs {
+        channel: &'a ChannelId,
+    },
+    #[cfg(feature = "docs")]
+    Doc {
+        channel: &'a ChannelId,
+        doc: &'a DocId,
+    },
+    #[cfg(feature = "forums")]
+    Forum {
+        channel: &'a ChannelId,
+    },
+    #[cfg(feature = "list")]
+    ListItems {
+        channel: &'a ChannelId,
+    },
+    #[cfg(feature = "list")]
+    ListItem {
+        channel: &'a ChannelId,
+        item: &'a ListId,
+    },
+    #[cfg(feature = "list")]
+    ListItemComplete {
+        channel: &'a ChannelId,
+        item: &'a ListId,
+    },
+    Members {
+        server: &'a ServerId,
+    },
+    Member {
+        server: &'a ServerId,
+        user: &'a UserId,
+    },
+    MemberNickname {
+        server: &'a ServerId,
+        user: &'a UserId,
+    },
+    MemberRoles {
+        server: &'a ServerId,
+        user: &'a UserId,
+    },
+    MemberRole {
+        server: &'a ServerId,
+        user: &'a UserId,
+        role: &'a RoleId,
+    },
+    #[cfg(feature = "xp")]
+    MemberXp {
+        server: &'a ServerId,
+        user: &'a UserId,
+    },
+    #[cfg(feature = "xp")]
+    RoleXp {
+        server: &'a ServerId,
+        role: &'a RoleId,
+    },
+    #[cfg(feature = "social")]
+    MemberSocialLink {
+        server: &'a ServerId,
+        user: &'a UserId,
+        link_type: SocialMediaType,
+    },
+    Bans {
+        server: &'a ServerId,
+    },
+    Ban {
+        server: &'a ServerId,
+        user: &'a UserId,
+    },
+    GroupMember {
+        group: &'a GroupId,
+        user: &'a UserId,
+    },
+    UrlSignatures,
+    CurrentUser,
+}
+impl Route<'_> {
+    /// Path relative to the API base, e.g. `/channels/{channelId}/messages`, with the IDs percent-encoded
+    pub fn path(&self) -> String {
+        match self {
+            Self::Channels => "/channels".to_owned(),
+            Self::Channel { channel } => format!("/channels/{}", segment(channel)),
+            Self::ChannelMessages { channel } => format!("/channels/{}/messages", segment(channel)),
+            Self::ChannelMessage { channel, message } => {
+                format!(
+                    "/channels/{}/messages/{}",
+                    segment(channel),
+                    segment(message)
+                )
+            }
+            #[cfg(feature = "calendar")]
+            Self::CalendarEventRsvps { channel, event } => format!(
+                "/channels/{}/events/{}/rsvps",
+                segment(channel),
+                segment(event)
+            ),
+            Self::ContentEmote {
+                channel,
+                content,
+                emote,
+            } => format!(
+                "/channels/{}/content/{}/emotes/{}",
+                segment(channel),
+                segment(content),
+                segment(emote)
+            ),
+            #[cfg(feature = "docs")]
+            Self::Docs { channel } => format!("/channels/{}/docs", segment(channel)),
+            #[cfg(feature = "docs")]
+            Self::Doc { channel, doc } => {
+                format!("/channels/{}/docs/{}", segment(channel), segment(doc))
+            }
+            #[cfg(feature = "forums")]
+            Self::Forum { channel } => format!("/channels/{}/forum", segment(channel)),
+            #[cfg(feature = "list")]
+            Self::ListItems { channel } => format!("/channels/{}/items", segment(channel)),
+            #[cfg(feature = "list")]
+            Self::ListItem { channel, item } => {
+                format!("/channels/{}/items/{}", segment(channel), segment(item))
+            }
+            #[cfg(feature = "list")]
+            Self::ListItemComplete { channel, item } => {
+                format!(
+                    "/channels/{}/items/{}/complete",
+                    segment(channel),
+                    segment(item)
+                )
+            }
+            Self::Members { server } => format!("/servers/{}/members", segment(server)),
+            Self::Member { server, user } => {
+                format!("/servers/{}/members/{}", segment(server), segment(user))
+            }
+            Self::MemberNickname { server, user } => {
+                format!(
+                    "/servers/{}/members/{}/nickname",
+                    segment(server),
+                    segment(user)
+                )
+            }
+            Self::MemberRoles { server, user } => {
+                format!(
+                    "/servers/{}/members/{}/roles",
+                    segment(server),
+                    segment(user)
+                )
+            }
+            Self::MemberRole { server, user, role } => {
+                format!(
+                    "/servers/{}/members/{}/roles/{}",
+                    segment(server),
+                    segment(user),
+                    segment(role)
+                )
+            }
+            #[cfg(feature = "xp")]
+            Self::MemberXp { server, user } => {
+                format!("/servers/{}/members/{}/xp", segment(server), segment(user))
+            }
+            #[cfg(feature = "xp")]
+            Self::RoleXp { server, role } => {
+                format!("/servers/{}/roles/{}/xp", segment(server), segment(role))
+            }
+            #[cfg(feature = "social")]
+            Self::MemberSocialLink {
+                server,
+                user,
+                link_type,
+            } => format!(
+                "/servers/{}/members/{}/social-links/{}",
+                segment(server),
+                segment(user),
+                segment(link_type)
+            ),
+            Self::Bans { server } => format!("/servers/{}/bans", segment(server)),
+            Self::Ban { server, user } => {
+                format!("/servers/{}/bans/{}", segment(server), segment(user))
+            }
+            Self::GroupMember { group, user } => {
+                format!("/groups/{}/members/{}", segment(group), segment(user))
+            }
+            Self::UrlSignatures => "/url-signatures".to_owned(),
+            Self::CurrentUser => "/users/@me".to_owned(),
+        }
+    }
+    /// Full URL of the endpoint on version 1 of the public API, see [`Http::url`](crate::http::Http::url) for the configured base
+    pub fn url(&self) -> String {
+        self.url_for(&ApiVersion::V1)
+    }
+    /// Full URL of the endpoint on `version` of the public API
+    pub fn url_for(&self, version: &ApiVersion) -> String {
+        format!("{}{}", version.base_url(), self.path())
+    }
+}
@@ -0,0 +1,71 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant, MissedTickBehavior};
+
+use crate::GuildedClient;
+
+/// Handle to a task started with [`GuildedClient::every`] or [`GuildedClient::cron`].
+///
+/// Dropping the handle leaves the task running; use [`ScheduledTask::cancel`] to stop it.
+#[derive(Debug)]
+pub struct ScheduledTask {
+    handle: JoinHandle<()>,
+}
+impl ScheduledTask {
+    pub(crate) fn every<F, Fut>(client: GuildedClient, period: Duration, mut task: F) -> Self
+    where
+        F: FnMut(GuildedClient) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        // Runs are traced as children of the span the task was scheduled in
+        #[cfg(feature = "otel")]
+        let parent = opentelemetry::Context::current();
+        let handle = tokio::spawn(async move {
+            let mut interval = time::interval_at(Instant::now() + period, period);
+            // A slow run pushes the following ones back rather than firing a burst to catch up
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let run = task(client.clone());
+                #[cfg(feature = "otel")]
+                let run = crate::otel::handler("scheduled task", &parent, run);
+                run.await;
+            }
+        });
+        Self { handle }
+    }
+    #[cfg(feature = "cron")]
+    pub(crate) fn cron<F, Fut>(client: GuildedClient, schedule: cron::Schedule, mut task: F) -> Self
+    where
+        F: FnMut(GuildedClient) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        // Runs are traced as children of the span the task was scheduled in
+        #[cfg(feature = "otel")]
+        let parent = opentelemetry::Context::current();
+        let handle = tokio::spawn(async move {
+            let mut after = chrono::Utc::now();
+            while let Some(next) = schedule.after(&after).next() {
+                let wait = (next - chrono::Utc::now()).to_std().unwrap_or_default();
+                time::sleep(wait).await;
+                let run = task(client.clone());
+                #[cfg(feature = "otel")]
+                let run = crate::otel::handler("scheduled task", &parent, run);
+                run.await;
+                // Times that passed during a long run are skipped rather than run back to back
+                after = chrono::Utc::now().max(next);
+            }
+        });
+        Self { handle }
+    }
+    /// Stops the task. A run that is currently in progress is aborted at its next await point.
+    pub fn cancel(&self) {
+        self.handle.abort();
+    }
+    /// Whether the task has stopped, either because it was cancelled or because its schedule ran out
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
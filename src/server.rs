@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::channel::ChannelId;
+use crate::member::{ServerId, UserId};
+
+api_enum! {
+    pub enum ServerType {
+        Team = "team",
+        Organization = "organization",
+        Community = "community",
+        Clan = "clan",
+        Guild = "guild",
+        Friends = "friends",
+        Streaming = "streaming",
+        Other = "other",
+    }
+}
+
+/// A server the bot is a member of
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Server {
+    id: ServerId,
+    #[serde(rename = "ownerId")]
+    owner: UserId,
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server_type: Option<ServerType>,
+    name: String,
+    /// Vanity URL slug, e.g. `Guilded-Official` for `guilded.gg/Guilded-Official`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    about: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    banner: Option<String>,
+    /// Timezone of the server as shown by Guilded, e.g. `America/Los Angeles (PST/PDT)`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timezone: Option<String>,
+    #[serde(rename = "isVerified")]
+    #[serde(default)]
+    verified: bool,
+    #[serde(rename = "defaultChannelId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_channel: Option<ChannelId>,
+    #[serde(rename = "createdAt")]
+    created: DateTime<Utc>,
+}
+impl Server {
+    pub fn id(&self) -> &ServerId {
+        &self.id
+    }
+    pub fn owner(&self) -> &UserId {
+        &self.owner
+    }
+    pub fn server_type(&self) -> Option<&ServerType> {
+        self.server_type.as_ref()
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Vanity URL slug of the server, if it has one
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+    pub fn about(&self) -> Option<&str> {
+        self.about.as_deref()
+    }
+    /// URL of the server's avatar, if it has one
+    pub fn avatar_url(&self) -> Option<Url> {
+        self.avatar.as_deref()?.parse().ok()
+    }
+    /// URL of the server's banner, if it has one
+    pub fn banner_url(&self) -> Option<Url> {
+        self.banner.as_deref()?.parse().ok()
+    }
+    pub fn timezone(&self) -> Option<&str> {
+        self.timezone.as_deref()
+    }
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
+    /// Channel new members land in, a good place for a welcome message
+    pub fn default_channel(&self) -> Option<ChannelId> {
+        self.default_channel
+    }
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+}
@@ -1,11 +1,19 @@
 use std::fmt::Display;
-use std::result::Result as StdResult;
 
-use reqwest::Client;
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use reqwest::{Request, StatusCode};
 use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+use tokio_stream::StreamExt;
 
-use crate::error::Result;
-use crate::member::{ServerId, UserId};
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
+use crate::member::{GetMembersRequest, ServerId, ServerMemberSummary, UserId};
+use crate::meta::ResponseMeta;
+use crate::routes::Route;
+use crate::streams::ApiStream;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -46,16 +54,61 @@ impl Display for SocialMediaType {
         write!(f, "{}", self.name())
     }
 }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SocialLink {
+    #[serde(rename = "type")]
+    link_type: SocialMediaType,
+    #[serde(rename = "userId")]
+    user: UserId,
+    /// Name of the account on the linked service
+    #[serde(skip_serializing_if = "Option::is_none")]
+    handle: Option<String>,
+    /// ID of the account on the linked service
+    #[serde(rename = "serviceId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_id: Option<String>,
+    #[serde(rename = "createdAt")]
+    created: DateTime<Utc>,
+}
+impl SocialLink {
+    pub fn link_type(&self) -> SocialMediaType {
+        self.link_type
+    }
+    pub fn user(&self) -> &UserId {
+        &self.user
+    }
+    pub fn handle(&self) -> Option<&str> {
+        self.handle.as_deref()
+    }
+    pub fn service_id(&self) -> Option<&str> {
+        self.service_id.as_deref()
+    }
+    pub fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+    #[cfg(feature = "time")]
+    pub fn created_time(&self) -> time::OffsetDateTime {
+        crate::timestamp::to_offset_date_time(&self.created)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GetSocialLinkResponse {
+    #[serde(rename = "socialLink")]
+    link: SocialLink,
+}
 #[derive(Debug)]
 pub struct GetSocialLinksRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
     link_type: SocialMediaType,
 }
 impl<'a> GetSocialLinksRequest<'a> {
     pub fn new(
-        client: Client,
+        client: Http,
         server: &'a ServerId,
         user: &'a UserId,
         link_type: SocialMediaType,
@@ -67,4 +120,94 @@ impl<'a> GetSocialLinksRequest<'a> {
             link_type,
         }
     }
+    /// Fetches the link, or `None` if the user hasn't linked an account of that type
+    pub async fn send(self) -> Result<Option<SocialLink>> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
+        let request = self
+            .client
+            .get(Route::MemberSocialLink {
+                server: self.server,
+                user: self.user,
+                link_type: self.link_type,
+            })
+            .build()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(Option<SocialLink>, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?;
+        let meta = ResponseMeta::new(&response);
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok((None, meta));
+        }
+        let link: GetSocialLinkResponse = response.check_status()?.json().await?;
+
+        Ok((Some(link.link), meta))
+    }
+}
+
+/// Members with their link, see [`MemberSocialLinksRequest::send`]
+pub type MemberSocialLinksStream<'a> = ApiStream<'a, (ServerMemberSummary, Option<SocialLink>)>;
+
+/// Fetches one type of social link for every member of a server, e.g. to verify accounts
+#[derive(Debug)]
+pub struct MemberSocialLinksRequest<'a> {
+    client: Http,
+    server: &'a ServerId,
+    link_type: SocialMediaType,
+    concurrency: usize,
+}
+impl<'a> MemberSocialLinksRequest<'a> {
+    pub fn new(client: Http, server: &'a ServerId, link_type: SocialMediaType) -> Self {
+        Self {
+            client,
+            server,
+            link_type,
+            concurrency: 4,
+        }
+    }
+    /// Yields each member with their link, in the order the lookups complete
+    pub fn send(self) -> MemberSocialLinksStream<'a> {
+        ApiStream::new(stream! {
+            let members = GetMembersRequest::new(self.client.clone(), self.server).send();
+            tokio::pin!(members);
+            let mut tasks = JoinSet::new();
+            let mut members_done = false;
+            loop {
+                while !members_done && tasks.len() < self.concurrency.max(1) {
+                    let Some(member) = members.next().await else {
+                        members_done = true;
+                        break;
+                    };
+                    let member = member?;
+                    let client = self.client.clone();
+                    let server = self.server.clone();
+                    let link_type = self.link_type;
+                    tasks.spawn(async move {
+                        let user = member.user().id().clone();
+                        let link = GetSocialLinksRequest::new(client, &server, &user, link_type)
+                            .send()
+                            .await;
+                        link.map(|link| (member, link))
+                    });
+                }
+                let Some(joined) = tasks.join_next().await else {
+                    break;
+                };
+                // Tasks are never aborted and lookups don't panic
+                yield joined.expect("social link task failed");
+            }
+        })
+    }
+    /// How many links are fetched at the same time (default: 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
 }
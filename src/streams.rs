@@ -0,0 +1,66 @@
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use chrono::{DateTime, Utc};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::error::Result;
+
+/// Stream of the items a request yields over several pages or lookups, such as
+/// [`MessagesStream`](crate::message::MessagesStream).
+///
+/// Unlike an `impl Stream` it can be named, e.g. to keep it in a struct field, and it's `Send`
+/// and `Unpin`, so it can be moved onto a spawned task and polled without pinning it first.
+pub struct ApiStream<'a, T> {
+    inner: Pin<Box<dyn Stream<Item = Result<T>> + Send + 'a>>,
+}
+impl<'a, T> ApiStream<'a, T> {
+    pub(crate) fn new(stream: impl Stream<Item = Result<T>> + Send + 'a) -> Self {
+        Self {
+            inner: Box::pin(stream),
+        }
+    }
+    /// Reads every item into a `Vec`, stopping at the first error
+    pub async fn collect_all(self) -> Result<Vec<T>> {
+        self.collect().await
+    }
+}
+impl<T> Stream for ApiStream<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+impl<T> Debug for ApiStream<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiStream").finish_non_exhaustive()
+    }
+}
+
+/// A single page of a paginated request, e.g. from
+/// [`GetChannelMessagesRequest::send_page`](crate::message::GetChannelMessagesRequest::send_page)
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    items: Vec<T>,
+    next: Option<DateTime<Utc>>,
+}
+impl<T> Page<T> {
+    pub(crate) fn new(items: Vec<T>, next: Option<DateTime<Utc>>) -> Self {
+        Self { items, next }
+    }
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+    /// Cursor of the following page, to pass to the request's `before`; `None` on the last page
+    pub fn next(&self) -> Option<DateTime<Utc>> {
+        self.next
+    }
+}
@@ -0,0 +1,28 @@
+//! Conversions between the `chrono` timestamps used by the models and `time::OffsetDateTime`
+//!
+//! Every timestamp accessor on the models has a `_time` counterpart returning an
+//! `OffsetDateTime`, e.g. [`Doc::created_time`](crate::docs::Doc::created_time) next to `created`.
+
+use chrono::{DateTime, TimeZone, Utc};
+use time::OffsetDateTime;
+
+/// Converts a timestamp to an [`OffsetDateTime`] in UTC.
+///
+/// # Panics
+/// If the timestamp is outside of the years supported by `time` (±9999).
+/// Timestamps returned by the API are always in range.
+pub fn to_offset_date_time(timestamp: &DateTime<Utc>) -> OffsetDateTime {
+    let nanos = i128::from(timestamp.timestamp()) * 1_000_000_000
+        + i128::from(timestamp.timestamp_subsec_nanos());
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).expect("timestamp out of range for time")
+}
+
+/// Converts an [`OffsetDateTime`] to UTC, for the builder methods that take a `chrono` timestamp
+pub fn from_offset_date_time(timestamp: OffsetDateTime) -> DateTime<Utc> {
+    let nanos = timestamp.unix_timestamp_nanos();
+    let seconds = nanos.div_euclid(1_000_000_000) as i64;
+    let subsec = nanos.rem_euclid(1_000_000_000) as u32;
+    Utc.timestamp_opt(seconds, subsec)
+        .single()
+        .expect("every OffsetDateTime fits in a chrono timestamp")
+}
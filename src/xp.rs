@@ -1,10 +1,13 @@
-use reqwest::Client;
+use reqwest::Request;
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
+use crate::dry_run::DryRun;
+use crate::error::{ResponseExt, Result};
+use crate::http::Http;
 use crate::member::{ServerId, UserId};
+use crate::meta::ResponseMeta;
 use crate::roles::RoleId;
-use crate::API_BASE;
+use crate::routes::Route;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct MemberXpResponse {
@@ -21,13 +24,13 @@ impl MemberXpBody {
 }
 #[derive(Debug)]
 pub struct MemberXpRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     user: &'a UserId,
     amount: i32,
 }
 impl<'a> MemberXpRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, user: &'a UserId, amount: i32) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, user: &'a UserId, amount: i32) -> Self {
         Self {
             client,
             server,
@@ -36,19 +39,31 @@ impl<'a> MemberXpRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<i32> {
+        self.send_with_meta().await.map(|(value, _)| value)
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let body = MemberXpBody::new(self.amount);
         let request = self
             .client
-            .post(format!(
-                "{API_BASE}/servers/{}/members/{}/xp",
-                self.server, self.user
-            ))
+            .post(Route::MemberXp {
+                server: self.server,
+                user: self.user,
+            })
             .json(&body)
             .build()?;
-        let response = self.client.execute(request).await?.error_for_status()?;
+
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<(i32, ResponseMeta)> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
         let total: MemberXpResponse = response.json().await?;
 
-        Ok(total.total)
+        Ok((total.total, meta))
     }
 }
 
@@ -63,13 +78,13 @@ impl RoleXpBody {
 }
 #[derive(Debug)]
 pub struct RoleXpRequest<'a> {
-    client: Client,
+    client: Http,
     server: &'a ServerId,
     role: &'a RoleId,
     amount: i32,
 }
 impl<'a> RoleXpRequest<'a> {
-    pub fn new(client: Client, server: &'a ServerId, role: &'a RoleId, amount: i32) -> Self {
+    pub fn new(client: Http, server: &'a ServerId, role: &'a RoleId, amount: i32) -> Self {
         Self {
             client,
             server,
@@ -78,17 +93,29 @@ impl<'a> RoleXpRequest<'a> {
         }
     }
     pub async fn send(self) -> Result<()> {
+        self.send_with_meta().await.map(|_| ())
+    }
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.build_request().map(|request| DryRun::new(&request))
+    }
+    fn build_request(&self) -> Result<Request> {
         let body = RoleXpBody::new(self.amount);
         let request = self
             .client
-            .post(format!(
-                "{API_BASE}/servers/{}/roles/{}/xp",
-                self.server, self.role
-            ))
+            .post(Route::RoleXp {
+                server: self.server,
+                role: self.role,
+            })
             .json(&body)
             .build()?;
-        let _response = self.client.execute(request).await?.error_for_status()?;
 
-        Ok(())
+        Ok(request)
+    }
+    pub async fn send_with_meta(self) -> Result<ResponseMeta> {
+        let request = self.build_request()?;
+        let response = self.client.execute(request).await?.check_status()?;
+        let meta = ResponseMeta::new(&response);
+
+        Ok(meta)
     }
 }
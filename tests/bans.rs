@@ -0,0 +1,65 @@
+mod common;
+
+use common::Reply;
+use guilded_rs::bans::MAX_BAN_REASON_LENGTH;
+use guilded_rs::error::{Constraint, Error};
+use guilded_rs::fixtures::{self, client};
+use guilded_rs::member::{ServerId, UserId};
+use guilded_rs::GuildedClient;
+
+#[test]
+fn ban_reason_is_sent() {
+    let client = client();
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let user: UserId = fixtures::USER_ID.parse().unwrap();
+    let request = client.ban_user(&server, &user).reason("spam");
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(body, serde_json::json!({ "reason": "spam" }));
+}
+
+#[test]
+fn ban_reason_too_long_is_rejected() {
+    let client = client();
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let user: UserId = fixtures::USER_ID.parse().unwrap();
+    let reason = "a".repeat(MAX_BAN_REASON_LENGTH + 1);
+    let error = client
+        .ban_user(&server, &user)
+        .reason(&reason)
+        .dry_run()
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Validation {
+            field: "reason",
+            constraint: Constraint::MaxLength(MAX_BAN_REASON_LENGTH),
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn revoke_deletes_the_ban_in_the_given_server() {
+    let (base_url, requests) = common::mock_server(vec![Reply::new(204, "")]).await;
+    let client = GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+
+    fixtures::server_member_ban()
+        .revoke(&client, &server)
+        .await
+        .unwrap();
+    assert_eq!(
+        requests.lock().unwrap()[0].trim(),
+        format!(
+            "DELETE /api/v1/servers/{}/bans/{}",
+            fixtures::SERVER_ID,
+            fixtures::USER_ID
+        )
+    );
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use common::Reply;
+use guilded_rs::error::Error;
+use guilded_rs::fixtures::{self, client};
+use guilded_rs::member::{ServerId, UserId};
+use guilded_rs::GuildedClient;
+use tokio::time::Instant;
+
+#[tokio::test]
+async fn batch_keeps_input_order_and_limits_concurrency() {
+    let running = AtomicUsize::new(0);
+    let most = AtomicUsize::new(0);
+    let mut batch = client().batch().concurrency(2);
+    for i in 0..6u64 {
+        let (running, most) = (&running, &most);
+        batch = batch.push(async move {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most.fetch_max(now, Ordering::SeqCst);
+            // Later requests finish first
+            tokio::time::sleep(Duration::from_millis(30 - 5 * i)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+            if i == 3 {
+                Err(Error::MissingToken("GUILDED_TOKEN".to_owned()))
+            } else {
+                Ok(i)
+            }
+        });
+    }
+    let results = batch.send().await;
+
+    assert_eq!(most.load(Ordering::SeqCst), 2);
+    assert_eq!(results.len(), 6);
+    for (i, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(value) => assert_eq!(value, i as u64),
+            Err(_) => assert_eq!(i, 3),
+        }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limited_batches_hold_off_every_request() {
+    let (base_url, requests) = common::mock_server(vec![
+        Reply::new(429, "{}").header("retry-after", 4),
+        Reply::new(204, ""),
+        Reply::new(204, ""),
+        Reply::new(204, ""),
+    ])
+    .await;
+    let client = GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let users: Vec<UserId> = [fixtures::USER_ID, "4WPbEZwd", "mq1ezklm"]
+        .iter()
+        .map(|user| user.parse().unwrap())
+        .collect();
+
+    let start = Instant::now();
+    let mut batch = client.batch().concurrency(2);
+    for user in &users {
+        let kick = client.kick_member(&server, user).send();
+        batch = batch.push(async move { kick.await.map(|()| start.elapsed()) });
+    }
+    let mut elapsed: Vec<Duration> = batch.send().await.into_iter().map(Result::unwrap).collect();
+    elapsed.sort();
+
+    // The third kick starts once the second is done, but still waits for the first's rate limit
+    assert_eq!(
+        elapsed,
+        [
+            Duration::ZERO,
+            Duration::from_secs(4),
+            Duration::from_secs(4)
+        ]
+    );
+    assert_eq!(requests.lock().unwrap().len(), 4);
+}
@@ -0,0 +1,27 @@
+#![cfg(all(feature = "calendar", feature = "chrono-tz"))]
+
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Europe::Berlin;
+use guilded_rs::calendar::{local_to_utc, utc_to_local};
+
+fn local(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2022, 3, day)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+#[test]
+fn local_times_follow_dst() {
+    let before = local_to_utc(local(26, 20, 0), Berlin).unwrap();
+    let after = local_to_utc(local(27, 20, 0), Berlin).unwrap();
+
+    assert_eq!(before.to_rfc3339(), "2022-03-26T19:00:00+00:00");
+    assert_eq!(after.to_rfc3339(), "2022-03-27T18:00:00+00:00");
+    assert_eq!(utc_to_local(&after, Berlin).naive_local(), local(27, 20, 0));
+}
+
+#[test]
+fn skipped_local_times_are_rejected() {
+    assert_eq!(local_to_utc(local(27, 2, 30), Berlin), None);
+}
@@ -0,0 +1,23 @@
+use guilded_rs::cdn::{resize, ImageSize};
+use reqwest::Url;
+
+#[test]
+fn resize_swaps_the_size_variant() {
+    let url: Url = "https://img.guildedcdn.com/UserAvatar/6dc417befe51bbca91b902984f113f89-Large.webp?w=450&h=450"
+        .parse()
+        .unwrap();
+
+    assert_eq!(
+        resize(&url, ImageSize::Small).as_str(),
+        "https://img.guildedcdn.com/UserAvatar/6dc417befe51bbca91b902984f113f89-Small.webp"
+    );
+}
+
+#[test]
+fn resize_keeps_default_assets() {
+    let url: Url = "https://img.guildedcdn.com/asset/DefaultUserAvatars/profile_1.png"
+        .parse()
+        .unwrap();
+
+    assert_eq!(resize(&url, ImageSize::Large), url);
+}
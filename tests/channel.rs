@@ -0,0 +1,106 @@
+use guilded_rs::channel::{CategoryId, ChannelId, ChannelType, ChannelVisibility};
+use guilded_rs::error::{Constraint, Error};
+use guilded_rs::fixtures::{self, client};
+
+#[test]
+#[allow(deprecated)]
+fn create_channel_public_is_a_bool() {
+    let client = client();
+    let request = client
+        .create_channel(fixtures::SERVER_ID, "general", ChannelType::Chat)
+        .public(true);
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(body["isPublic"], serde_json::Value::Bool(true));
+}
+
+#[test]
+fn create_channel_omits_public_by_default() {
+    let client = client();
+    let request = client.create_channel(fixtures::SERVER_ID, "general", ChannelType::Chat);
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert!(body.get("isPublic").is_none());
+}
+
+#[test]
+fn create_channel_visibility() {
+    let client = client();
+    let request = client
+        .create_channel(fixtures::SERVER_ID, "general", ChannelType::Chat)
+        .visibility(ChannelVisibility::Private);
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(body["visibility"], "private");
+}
+
+#[test]
+fn update_channel_resets_visibility() {
+    let client = client();
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+    let request = client.update_channel(&channel).visibility(None);
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(body, serde_json::json!({ "visibility": null }));
+}
+
+#[test]
+fn category_id_accepts_numbers_and_strings() {
+    let number: CategoryId = serde_json::from_str("42").unwrap();
+    let string: CategoryId = serde_json::from_str("\"42\"").unwrap();
+
+    assert_eq!(number, string);
+    assert_eq!(serde_json::to_string(&string).unwrap(), "42");
+    assert!(serde_json::from_str::<CategoryId>("\"general\"").is_err());
+}
+
+#[test]
+fn create_channel_reports_the_invalid_field() {
+    let client = client();
+    let long_name = "a".repeat(101);
+    let long_topic = "a".repeat(513);
+    let cases = [
+        (
+            client.create_channel(fixtures::SERVER_ID, "", ChannelType::Chat),
+            "name",
+            Constraint::MinLength(1),
+        ),
+        (
+            client.create_channel(fixtures::SERVER_ID, &long_name, ChannelType::Chat),
+            "name",
+            Constraint::MaxLength(100),
+        ),
+        (
+            client
+                .create_channel(fixtures::SERVER_ID, "general", ChannelType::Chat)
+                .topic(&long_topic),
+            "topic",
+            Constraint::MaxLength(512),
+        ),
+    ];
+    for (request, field, constraint) in cases {
+        match request.dry_run() {
+            Err(Error::Channel(e)) => {
+                assert_eq!(e.field(), field);
+                assert_eq!(e.constraint(), constraint);
+            }
+            other => panic!("expected an invalid {field}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn create_channel_leaves_categories_to_the_server() {
+    let client = client();
+    let category: CategoryId = serde_json::from_str("42").unwrap();
+    let request = client
+        .create_channel(fixtures::SERVER_ID, "general", ChannelType::Chat)
+        .category(&category);
+
+    // Without a group the category has to be in the home group, which only the server knows
+    assert!(request.dry_run().is_ok());
+}
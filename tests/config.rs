@@ -0,0 +1,37 @@
+use guilded_rs::channel::ChannelId;
+use guilded_rs::config::GuildedConfig;
+use guilded_rs::fixtures;
+use guilded_rs::GuildedClient;
+
+#[test]
+fn from_config_uses_base_url() {
+    let config: GuildedConfig = serde_json::from_value(serde_json::json!({
+        "token": { "value": fixtures::TOKEN },
+        "base_url": "http://localhost:8080/api/v1/",
+        "rate_limit": { "retries": 2, "throttle": { "max": 5, "period_ms": 5000 } }
+    }))
+    .unwrap();
+    let client = GuildedClient::from_config(&config).unwrap();
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+    let url = client
+        .get_messages(&channel)
+        .dry_run()
+        .unwrap()
+        .url()
+        .clone();
+
+    assert_eq!(
+        url.as_str(),
+        format!(
+            "http://localhost:8080/api/v1/channels/{}/messages",
+            fixtures::CHANNEL_ID
+        )
+    );
+}
+
+#[test]
+fn config_rejects_unknown_fields() {
+    let config = serde_json::from_value::<GuildedConfig>(serde_json::json!({ "timeout": 10 }));
+
+    assert!(config.is_err());
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "docs")]
+
+mod common;
+
+use common::Reply;
+use guilded_rs::channel::ChannelId;
+use guilded_rs::docs::DocId;
+use guilded_rs::error::{Constraint, Error};
+use guilded_rs::fixtures::{self, client};
+use guilded_rs::GuildedClient;
+
+#[test]
+fn update_doc_needs_a_title_or_content() {
+    let client = client();
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+    let doc: DocId = "1".parse().unwrap();
+
+    assert!(matches!(
+        client.update_doc(&channel, &doc).dry_run(),
+        Err(Error::Validation {
+            field: "title_or_content",
+            constraint: Constraint::Required,
+            ..
+        })
+    ));
+    assert!(client
+        .update_doc(&channel, &doc)
+        .title("Rules")
+        .dry_run()
+        .is_ok());
+}
+
+#[tokio::test]
+async fn update_doc_with_both_fields_skips_the_fetch() {
+    let updated = format!(r#"{{"doc": {}}}"#, fixtures::DOC);
+    let (base_url, requests) = common::mock_server(vec![Reply::new(200, updated)]).await;
+    let client = GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+    let doc: DocId = "1".parse().unwrap();
+
+    client
+        .update_doc(&channel, &doc)
+        .title("Rules")
+        .content("Be nice")
+        .send()
+        .await
+        .unwrap();
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("PUT "));
+}
@@ -0,0 +1,35 @@
+use guilded_rs::emotes::{self, EmoteRegistry};
+use guilded_rs::reactions::EmoteId;
+
+#[test]
+fn defaults_are_seeded() {
+    let registry = EmoteRegistry::with_defaults();
+    assert_eq!(registry.get(":grinning:"), Some(&emotes::grinning()));
+    assert_eq!(registry.shortcode(&emotes::grinning()), Some("grinning"));
+    assert!(EmoteRegistry::new().get("grinning").is_none());
+}
+
+#[test]
+fn shortcode_follows_inserts_and_removals() {
+    let party = EmoteId::new(1);
+    let mut registry = EmoteRegistry::with_defaults()
+        .with(":Party:", party)
+        .with("celebrate", party);
+    assert_eq!(registry.shortcode(&party), Some("celebrate"));
+
+    registry.remove(":celebrate:");
+    assert_eq!(registry.shortcode(&party), Some("party"));
+
+    // Reusing the shortcode for another emote unlinks the old one
+    registry.insert("party", EmoteId::new(2));
+    assert_eq!(registry.shortcode(&party), None);
+    assert_eq!(registry.shortcode(&EmoteId::new(2)), Some("party"));
+}
+
+#[test]
+fn shortcodes_without_a_default_are_registered_by_hand() {
+    let check = EmoteId::new(1);
+    let registry = EmoteRegistry::with_defaults().with("white_check_mark", check);
+    assert_eq!(registry.get(":white_check_mark:"), Some(&check));
+    assert_eq!(registry.get(":grinning:"), Some(&emotes::grinning()));
+}
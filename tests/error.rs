@@ -0,0 +1,60 @@
+mod common;
+
+use std::time::Duration;
+
+use common::Reply;
+use guilded_rs::channel::ChannelId;
+use guilded_rs::error::Error;
+use guilded_rs::fixtures;
+use guilded_rs::member::{ServerId, UserId};
+use guilded_rs::message::MessageId;
+use guilded_rs::GuildedClient;
+use reqwest::{Method, StatusCode};
+
+async fn client(replies: Vec<Reply>) -> GuildedClient {
+    let (base_url, _) = common::mock_server(replies).await;
+    GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .rate_limit_retries(0)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn status_errors_say_which_request_failed() {
+    let client = client(vec![
+        Reply::new(401, "{}"),
+        Reply::new(429, "{}").header("retry-after", 3),
+        Reply::new(404, "{}"),
+    ])
+    .await;
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+    let message: MessageId = fixtures::MESSAGE_ID.parse().unwrap();
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let user: UserId = fixtures::USER_ID.parse().unwrap();
+
+    let unauthorized = client.get_message(&channel, &message).send().await;
+    let Err(e @ Error::Unauthorized(_)) = unauthorized else {
+        panic!("expected unauthorized, got {unauthorized:?}");
+    };
+    let context = e.context().unwrap();
+    assert_eq!(context.method(), Some(&Method::GET));
+    assert_eq!(context.route(), "/channels/:id/messages/:id");
+    assert_eq!(e.status(), Some(StatusCode::UNAUTHORIZED));
+
+    let rate_limited = client.kick_member(&server, &user).send().await;
+    let Err(e @ Error::RateLimited { retry_after, .. }) = rate_limited else {
+        panic!("expected rate limited, got {rate_limited:?}");
+    };
+    assert_eq!(retry_after, Some(Duration::from_secs(3)));
+    assert_eq!(e.context().unwrap().method(), Some(&Method::DELETE));
+    assert_eq!(e.context().unwrap().route(), "/servers/:id/members/:id");
+    assert_eq!(e.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+
+    let not_found = client.get_message(&channel, &message).send().await;
+    let Err(e @ Error::NotFound { .. }) = not_found else {
+        panic!("expected not found, got {not_found:?}");
+    };
+    assert_eq!(e.context().unwrap().status(), StatusCode::NOT_FOUND);
+    assert_eq!(e.context().unwrap().route(), "/channels/:id/messages/:id");
+}
@@ -0,0 +1,106 @@
+use guilded_rs::emotes;
+use guilded_rs::events::{ChannelMessageReactionCreated, Opcode, RawFrame};
+use guilded_rs::fixtures;
+use guilded_rs::member::UserType;
+
+#[test]
+fn chat_message_parses() {
+    let message = fixtures::chat_message();
+
+    assert_eq!(message.id().to_string(), fixtures::MESSAGE_ID);
+    assert_eq!(message.content(), "Hello **world**!");
+}
+
+#[test]
+fn server_member_summary_parses() {
+    let member = fixtures::server_member_summary();
+
+    assert_eq!(**member.user().id(), fixtures::USER_ID);
+}
+
+#[test]
+fn every_fixture_parses() {
+    fixtures::user();
+    fixtures::user_summary();
+    fixtures::server();
+    fixtures::server_member();
+    fixtures::server_channel();
+    fixtures::server_member_ban();
+    fixtures::reaction();
+    #[cfg(feature = "docs")]
+    fixtures::doc();
+    #[cfg(feature = "forums")]
+    fixtures::forum_thread();
+    #[cfg(feature = "list")]
+    fixtures::list_item();
+    #[cfg(feature = "list")]
+    fixtures::list_item_summary();
+    #[cfg(feature = "list")]
+    fixtures::list_note();
+    fixtures::uploaded_media();
+    fixtures::url_signature();
+    fixtures::export_checkpoint();
+    #[cfg(feature = "social")]
+    fixtures::social_link();
+    fixtures::server_channel_created();
+}
+
+#[test]
+fn user_parses() {
+    let user = fixtures::user();
+
+    assert_eq!(**user.id(), fixtures::USER_ID);
+    assert_eq!(user.user_type(), &UserType::User);
+    let status = user.status().unwrap();
+    assert_eq!(status.content(), Some("Doing some coding"));
+    assert_eq!(status.emote(), emotes::grinning());
+}
+
+#[test]
+fn reaction_event_parses() {
+    let event = fixtures::channel_message_reaction_created();
+
+    assert_eq!(event.reaction().emote().name(), "grinning");
+    assert_eq!(**event.reaction().user(), fixtures::USER_ID);
+}
+
+#[test]
+fn roles_updated_diffs_against_the_cached_member() {
+    let member = fixtures::server_member();
+    let event = fixtures::server_roles_updated();
+    let roles = &event.members()[0];
+    let added: Vec<_> = roles.added(member.roles()).map(|role| *role).collect();
+    let removed: Vec<_> = roles.removed(member.roles()).map(|role| *role).collect();
+
+    assert_eq!(added, [35]);
+    assert_eq!(removed, [28]);
+}
+
+#[test]
+fn bot_added_event_has_the_default_channel() {
+    let event = fixtures::bot_server_membership_created();
+
+    assert_eq!(
+        event
+            .server()
+            .default_channel()
+            .map(|channel| channel.to_string()),
+        Some(fixtures::CHANNEL_ID.to_owned())
+    );
+    assert_eq!(**event.invited_by(), fixtures::USER_ID);
+}
+
+#[test]
+fn raw_frames_parse_into_events() {
+    let frame: RawFrame = serde_json::from_value(serde_json::json!({
+        "op": 0,
+        "t": "ChannelMessageReactionCreated",
+        "d": serde_json::from_str::<serde_json::Value>(fixtures::CHANNEL_MESSAGE_REACTION_CREATED).unwrap(),
+        "s": "c7d5f8a2-1b1e-4a3f-9c55-0d2e6b6c9a41"
+    }))
+    .unwrap();
+    let event: ChannelMessageReactionCreated = frame.parse().unwrap();
+
+    assert_eq!(frame.op(), Opcode::Event);
+    assert_eq!(event.reaction().emote().name(), "grinning");
+}
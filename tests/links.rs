@@ -0,0 +1,66 @@
+use guilded_rs::channel::ChannelId;
+use guilded_rs::fixtures;
+use guilded_rs::groups::GroupId;
+use guilded_rs::links::{ChannelLink, LinkServer};
+use guilded_rs::member::ServerId;
+use guilded_rs::message::MessageId;
+
+fn message_link() -> String {
+    format!(
+        "https://www.guilded.gg/teams/{}/groups/z7DxEO8d/channels/{}/chat?messageId={}",
+        fixtures::SERVER_ID,
+        fixtures::CHANNEL_ID,
+        fixtures::MESSAGE_ID
+    )
+}
+
+#[test]
+fn parse_message_link() {
+    let link: ChannelLink = message_link().parse().unwrap();
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let group: GroupId = "z7DxEO8d".parse().unwrap();
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+    let message: MessageId = fixtures::MESSAGE_ID.parse().unwrap();
+
+    assert_eq!(link.server_id(), Some(&server));
+    assert_eq!(link.group(), Some(&group));
+    assert_eq!(link.channel(), &channel);
+    assert_eq!(link.message_id(), Some(&message));
+    assert_eq!(
+        ChannelLink::new(&server, &group, &channel)
+            .message(&message)
+            .to_string(),
+        message_link()
+    );
+}
+
+#[test]
+fn parse_vanity_channel_link() {
+    let link: ChannelLink = format!(
+        "guilded.gg/Guilded-Official/channels/{}/chat",
+        fixtures::CHANNEL_ID
+    )
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        link.server(),
+        &LinkServer::Vanity("Guilded-Official".to_owned())
+    );
+    assert_eq!(link.server_id(), None);
+    assert_eq!(link.message_id(), None);
+}
+
+#[test]
+fn parse_rejects_other_links() {
+    assert!(format!(
+        "https://example.com/teams/{}/channels/{}/chat",
+        fixtures::SERVER_ID,
+        fixtures::CHANNEL_ID
+    )
+    .parse::<ChannelLink>()
+    .is_err());
+    assert!("https://www.guilded.gg/Guilded-Official/overview"
+        .parse::<ChannelLink>()
+        .is_err());
+}
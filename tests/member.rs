@@ -0,0 +1,46 @@
+mod common;
+
+use common::Reply;
+use guilded_rs::error::Error;
+use guilded_rs::fixtures;
+use guilded_rs::member::{ServerId, UserId};
+use guilded_rs::GuildedClient;
+
+async fn client_listing(members: &[&str]) -> GuildedClient {
+    let body = format!(r#"{{"members": [{}]}}"#, members.join(","));
+    let (base_url, _) = common::mock_server(vec![Reply::new(200, body)]).await;
+    GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn resume_after_skips_up_to_the_checkpoint() {
+    let client = client_listing(&[fixtures::SERVER_MEMBER_SUMMARY]).await;
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let user: UserId = fixtures::USER_ID.parse().unwrap();
+    let members = client
+        .get_members(&server)
+        .resume_after(&user)
+        .collect_all()
+        .await
+        .unwrap();
+    assert!(members.is_empty());
+}
+
+#[tokio::test]
+async fn resume_after_a_user_not_listed_is_not_found() {
+    let client = client_listing(&[fixtures::SERVER_MEMBER_SUMMARY]).await;
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let gone: UserId = "Gone1234".parse().unwrap();
+    let result = client
+        .get_members(&server)
+        .resume_after(&gone)
+        .collect_all()
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::NotFound { resource: "member", id, context: None }) if id == "Gone1234"
+    ));
+}
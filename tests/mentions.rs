@@ -0,0 +1,57 @@
+use guilded_rs::channel::ChannelId;
+use guilded_rs::fixtures;
+use guilded_rs::member::UserId;
+use guilded_rs::mentions::{self, Mention};
+use guilded_rs::roles::RoleId;
+
+#[test]
+fn parse_mentions_with_spans() {
+    let content = format!(
+        "<@{}> moved to <#{}>, ping <@591232>",
+        fixtures::USER_ID,
+        fixtures::CHANNEL_ID
+    );
+    let found: Vec<_> = mentions::parse(&content).collect();
+    let user: UserId = fixtures::USER_ID.parse().unwrap();
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+
+    assert_eq!(
+        found,
+        vec![
+            (0..11, Mention::User(user)),
+            (21..60, Mention::Channel(channel)),
+            (67..76, Mention::Role(RoleId::new(591232))),
+        ]
+    );
+}
+
+#[test]
+fn mention_round_trips() {
+    let user: UserId = fixtures::USER_ID.parse().unwrap();
+    let mention = Mention::from(user);
+
+    assert_eq!(mention.to_string(), "<@Ann6LewA>");
+    assert_eq!("<@Ann6LewA>".parse(), Ok(mention));
+    assert!("@Ann6LewA".parse::<Mention>().is_err());
+    assert!("<@Ann 6>".parse::<Mention>().is_err());
+}
+
+#[test]
+fn users_skips_other_markup() {
+    let content = "a <b> <@> <@Ann6LewA> <#nope>";
+
+    assert_eq!(mentions::users(content).count(), 1);
+    assert_eq!(mentions::channels(content).count(), 0);
+}
+
+#[test]
+fn all_digit_user_ids_are_users() {
+    let user: UserId = "12345678".parse().unwrap();
+
+    assert_eq!("<@12345678>".parse(), Ok(Mention::User(user)));
+    assert_eq!(
+        "<@1234567>".parse(),
+        Ok(Mention::Role(RoleId::new(1234567)))
+    );
+    assert_eq!("<@31>".parse(), Ok(Mention::Role(RoleId::new(31))));
+}
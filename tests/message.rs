@@ -0,0 +1,196 @@
+use chrono::{DateTime, Utc};
+use guilded_rs::channel::ChannelId;
+use guilded_rs::error::Error;
+use guilded_rs::fixtures::{self, client};
+use guilded_rs::message::{MessageError, MessageId, MessagesStream, MAX_CONTENT_LENGTH};
+use guilded_rs::routes::ApiVersion;
+use guilded_rs::GuildedClient;
+
+fn channel() -> ChannelId {
+    fixtures::CHANNEL_ID.parse().unwrap()
+}
+
+#[test]
+fn get_messages_include_private_query() {
+    let client = client();
+    let channel = channel();
+    let request = client.get_messages(&channel).include_private(true);
+    let url = request.dry_run().unwrap().url().clone();
+
+    assert_eq!(
+        url.as_str(),
+        "https://www.guilded.gg/api/v1/channels/00000000-0000-0000-0000-000000000000/messages?includePrivate=true"
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn get_messages_private_is_an_alias() {
+    let client = client();
+    let channel = channel();
+    let request = client.get_messages(&channel).private(false);
+    let url = request.dry_run().unwrap().url().clone();
+
+    assert_eq!(url.query(), Some("includePrivate=false"));
+}
+
+#[test]
+fn messages_between_includes_both_bounds() {
+    let client = client();
+    let channel = channel();
+    let start = "2022-05-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+    let end = "2022-05-31T23:59:59Z".parse::<DateTime<Utc>>().unwrap();
+    let request = client.messages_between(&channel, start, end);
+    let url = request.dry_run().unwrap().url().clone();
+    let query: Vec<_> = url.query_pairs().collect();
+
+    assert_eq!(
+        query,
+        [
+            ("before".into(), "2022-05-31T23:59:59.001Z".into()),
+            ("after".into(), "2022-04-30T23:59:59.999Z".into()),
+        ]
+    );
+}
+
+#[test]
+fn api_version_changes_the_base_url() {
+    let client = GuildedClient::builder(fixtures::TOKEN)
+        .api_version(ApiVersion::Other("v2".to_owned()))
+        .build()
+        .unwrap();
+    let channel = channel();
+    let url = client
+        .get_messages(&channel)
+        .dry_run()
+        .unwrap()
+        .url()
+        .clone();
+
+    assert_eq!(
+        url.as_str(),
+        "https://www.guilded.gg/api/v2/channels/00000000-0000-0000-0000-000000000000/messages"
+    );
+}
+
+#[tokio::test]
+async fn messages_stream_can_be_spawned() {
+    struct History {
+        messages: MessagesStream<'static>,
+    }
+    let channel: &'static ChannelId = Box::leak(Box::new(channel()));
+    let history = History {
+        messages: client().get_messages(channel).send(),
+    };
+
+    // Only checks that the stream can be moved onto a task, it's never polled
+    tokio::spawn(async move { drop(history.messages) })
+        .await
+        .unwrap();
+}
+
+#[test]
+fn update_message_leaves_embeds_alone_by_default() {
+    let client = client();
+    let channel = channel();
+    let message: MessageId = fixtures::MESSAGE_ID.parse().unwrap();
+    let request = client.update_message(&channel, &message, "edited");
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(body, serde_json::json!({ "content": "edited" }));
+}
+
+#[test]
+fn edit_message_clears_embeds_without_content() {
+    let client = client();
+    let channel = channel();
+    let message: MessageId = fixtures::MESSAGE_ID.parse().unwrap();
+    let request = client.edit_message(&channel, &message).clear_embeds();
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(body, serde_json::json!({ "embeds": [] }));
+    assert!(client.edit_message(&channel, &message).dry_run().is_err());
+}
+
+#[test]
+fn edit_message_hides_link_previews() {
+    let client = client();
+    let channel = channel();
+    let message: MessageId = fixtures::MESSAGE_ID.parse().unwrap();
+    let request = client
+        .update_message(&channel, &message, "see https://example.com/")
+        .hide_link_preview("https://example.com/")
+        .unwrap();
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "content": "see https://example.com/",
+            "hiddenLinkPreviewUrls": ["https://example.com/"],
+        })
+    );
+}
+
+#[test]
+fn hidden_link_previews_keep_the_url_as_written() {
+    let client = client();
+    let channel = channel();
+    let request = client
+        .send_message(&channel, "see https://example.com")
+        .hide_link_preview("https://example.com")
+        .unwrap();
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+
+    assert_eq!(
+        body["hiddenLinkPreviewUrls"],
+        serde_json::json!(["https://example.com"])
+    );
+    assert!(client
+        .send_message(&channel, "see example")
+        .hide_link_preview("not a url")
+        .is_err());
+}
+
+#[test]
+fn dry_run_checks_attachments_like_send() {
+    let client = client();
+    let channel = channel();
+    let missing = std::path::Path::new("does/not/exist.png");
+    let result = client
+        .send_message(&channel, "look")
+        .attach_image(missing)
+        .dry_run();
+
+    assert!(matches!(
+        result,
+        Err(Error::Message(MessageError::Attachment { path, .. })) if path == missing
+    ));
+}
+
+#[test]
+fn dry_run_of_split_message_shows_the_first_part() {
+    let client = client();
+    let channel = channel();
+    let reply: MessageId = fixtures::MESSAGE_ID.parse().unwrap();
+    let paragraph = "word ".repeat(MAX_CONTENT_LENGTH / 5);
+    let content = format!("{paragraph}\n\nsecond part");
+    let request = client
+        .send_message(&channel, content.as_str())
+        .add_reply(&reply)
+        .split_long();
+    let body: serde_json::Value =
+        serde_json::from_str(request.dry_run().unwrap().body().unwrap()).unwrap();
+    let sent = body["content"].as_str().unwrap();
+
+    assert!(sent.chars().count() <= MAX_CONTENT_LENGTH);
+    assert!(!sent.contains("second part"));
+    assert_eq!(
+        body["replyMessageIds"],
+        serde_json::json!([fixtures::MESSAGE_ID])
+    );
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::Reply;
+use guilded_rs::channel::ChannelId;
+use guilded_rs::error::Error;
+use guilded_rs::fixtures;
+use guilded_rs::message::{MessageError, MAX_CONTENT_LENGTH};
+use guilded_rs::outbox::MemoryStore;
+use guilded_rs::GuildedClient;
+
+#[tokio::test]
+async fn rejected_head_message_is_dropped_from_the_queue() {
+    let created = format!(r#"{{"message": {}}}"#, fixtures::CHAT_MESSAGE);
+    let (base_url, requests) = common::mock_server(vec![
+        Reply::new(503, "{}"),
+        Reply::new(400, r#"{"code": "BadRequestError", "message": "bad"}"#),
+        Reply::new(201, created),
+    ])
+    .await;
+    let client = GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+    let outbox = client.outbox(MemoryStore::new());
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+
+    // The server error queues the first message, which is then rejected when flushed ahead of the second
+    let queued = outbox.send(client.send_message(&channel, "first")).await;
+    assert!(queued.unwrap().is_none());
+    let sent = outbox.send(client.send_message(&channel, "second")).await;
+    assert!(sent.unwrap().is_some());
+
+    let rejected = outbox.take_rejected();
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0].0.body()["content"], "first");
+    assert_eq!(rejected[0].1.status().map(|s| s.as_u16()), Some(400));
+    assert!(outbox.flush().await.unwrap());
+    assert_eq!(requests.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn split_messages_are_queued_one_part_each() {
+    let created = format!(r#"{{"message": {}}}"#, fixtures::CHAT_MESSAGE);
+    let (base_url, requests) = common::mock_server(vec![
+        Reply::new(503, "{}"),
+        Reply::new(201, created.clone()),
+        Reply::new(201, created),
+    ])
+    .await;
+    let client = GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+    let outbox = client.outbox(MemoryStore::new());
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+    let content = format!("{}\n\n{}", "a".repeat(3000), "b".repeat(3000));
+
+    let queued = outbox
+        .send(client.send_message(&channel, content).split_long())
+        .await;
+    assert!(queued.unwrap().is_none());
+    assert!(outbox.flush().await.unwrap());
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[1].contains(&"a".repeat(3000)) && !requests[1].contains('b'));
+    assert!(requests[2].contains(&"b".repeat(3000)));
+}
+
+#[tokio::test]
+async fn long_messages_are_rejected_before_queueing() {
+    let (base_url, requests) = common::mock_server(Vec::new()).await;
+    let client = GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+    let outbox = client.outbox(MemoryStore::new());
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+
+    let result = outbox
+        .send(client.send_message(&channel, "a".repeat(MAX_CONTENT_LENGTH + 1)))
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::Message(MessageError::ContentTooLong { .. }))
+    ));
+    assert!(requests.lock().unwrap().is_empty());
+}
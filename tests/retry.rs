@@ -0,0 +1,91 @@
+mod common;
+
+use std::time::Duration;
+
+use common::Reply;
+use guilded_rs::channel::ChannelId;
+use guilded_rs::fixtures;
+use guilded_rs::member::{ServerId, UserId};
+use guilded_rs::reactions::EmoteId;
+use guilded_rs::GuildedClient;
+use tokio::time::Instant;
+
+async fn client(replies: Vec<Reply>) -> GuildedClient {
+    let (base_url, _) = common::mock_server(replies).await;
+    GuildedClient::builder(fixtures::TOKEN)
+        .base_url(&base_url)
+        .throttle_sends(1, Duration::from_secs(10), false)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limited_requests_wait_on_the_clock() {
+    let client = client(vec![
+        Reply::new(429, "{}").header("retry-after", 5),
+        Reply::new(200, "{}"),
+        Reply::new(429, "{}"),
+        Reply::new(200, "{}"),
+    ])
+    .await;
+    let message = fixtures::chat_message();
+
+    let start = Instant::now();
+    message
+        .add_reactions(&client, &[EmoteId::new(1), EmoteId::new(2)])
+        .await
+        .unwrap();
+    // The server's Retry-After, then the default wait for the 429 without one
+    assert_eq!(start.elapsed(), Duration::from_secs(6));
+}
+
+#[tokio::test(start_paused = true)]
+async fn throttled_sends_wait_on_the_clock() {
+    let created = format!(r#"{{"message": {}}}"#, fixtures::CHAT_MESSAGE);
+    let client = client(vec![
+        Reply::new(201, created.clone()),
+        Reply::new(201, created),
+    ])
+    .await;
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+
+    let start = Instant::now();
+    client.send_message(&channel, "first").send().await.unwrap();
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    client
+        .send_message(&channel, "second")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(start.elapsed(), Duration::from_secs(10));
+}
+
+#[tokio::test(start_paused = true)]
+async fn generated_requests_retry_when_rate_limited() {
+    let client = client(vec![
+        Reply::new(429, "{}").header("retry-after", 3),
+        Reply::new(204, ""),
+    ])
+    .await;
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let user: UserId = fixtures::USER_ID.parse().unwrap();
+
+    let start = Instant::now();
+    client.kick_member(&server, &user).send().await.unwrap();
+    assert_eq!(start.elapsed(), Duration::from_secs(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn sent_messages_retry_when_rate_limited() {
+    let created = format!(r#"{{"message": {}}}"#, fixtures::CHAT_MESSAGE);
+    let client = client(vec![
+        Reply::new(429, "{}").header("retry-after", 2),
+        Reply::new(201, created),
+    ])
+    .await;
+    let channel: ChannelId = fixtures::CHANNEL_ID.parse().unwrap();
+
+    let start = Instant::now();
+    client.send_message(&channel, "hello").send().await.unwrap();
+    assert_eq!(start.elapsed(), Duration::from_secs(2));
+}
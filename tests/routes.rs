@@ -0,0 +1,37 @@
+use guilded_rs::fixtures;
+use guilded_rs::member::{ServerId, UserId};
+use guilded_rs::routes::Route;
+use reqwest::Url;
+
+fn member_url(user: &str) -> Url {
+    let server: ServerId = fixtures::SERVER_ID.parse().unwrap();
+    let user: UserId = user.parse().unwrap();
+    Route::Member {
+        server: &server,
+        user: &user,
+    }
+    .url()
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn dot_segments_stay_inside_the_route() {
+    for user in [".", "..", "../bans", "%2e%2e"] {
+        let url = member_url(user);
+        let path = url.path();
+        assert!(
+            path.starts_with(&format!("/api/v1/servers/{}/members/", fixtures::SERVER_ID)),
+            "{user} resolved to {path}"
+        );
+        assert_eq!(path.matches('/').count(), 6, "{user} resolved to {path}");
+    }
+    assert_eq!(
+        member_url("..").path(),
+        format!("/api/v1/servers/{}/members/%252E%252E", fixtures::SERVER_ID)
+    );
+    assert_eq!(
+        member_url("a.b").path(),
+        format!("/api/v1/servers/{}/members/a%2Eb", fixtures::SERVER_ID)
+    );
+}